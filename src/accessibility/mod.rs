@@ -0,0 +1,37 @@
+pub mod components;
+pub mod resources;
+pub mod systems;
+
+use crate::{board::BoardSettings, game::GameState};
+use bevy::prelude::*;
+pub use components::*;
+pub use resources::*;
+use systems::*;
+
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AccessibilitySettings>()
+            .init_resource::<KeyboardCursor>()
+            .insert_resource(ScreenReaderBridge::from_env())
+            .add_systems(Startup, (setup_audio_cues, setup_cursor_highlight))
+            .add_systems(
+                Update,
+                (
+                    toggle_accessibility,
+                    clamp_cursor.run_if(resource_changed::<BoardSettings>),
+                    (
+                        handle_cursor_movement,
+                        handle_cursor_action.run_if(in_state(GameState::Playing)),
+                        announce_tile_changes,
+                        announce_game_end,
+                    )
+                        .chain()
+                        .run_if(accessibility_enabled),
+                    update_cursor_highlight,
+                )
+                    .chain(),
+            );
+    }
+}
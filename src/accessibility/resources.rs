@@ -0,0 +1,63 @@
+use crate::tile::Position;
+use bevy::prelude::*;
+
+#[derive(Resource, Debug, Default)]
+pub struct AccessibilitySettings {
+    pub enabled: bool,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct KeyboardCursor {
+    pub position: Position,
+}
+
+/// Bridge to a text-to-speech engine or platform screen reader.
+pub trait ScreenReader: Send + Sync + 'static {
+    fn announce(&mut self, message: &str);
+}
+
+pub struct LogScreenReader;
+
+impl ScreenReader for LogScreenReader {
+    fn announce(&mut self, message: &str) {
+        info!("{message}");
+    }
+}
+
+pub struct StdoutScreenReader;
+
+impl ScreenReader for StdoutScreenReader {
+    fn announce(&mut self, message: &str) {
+        println!("{message}");
+    }
+}
+
+#[derive(Resource)]
+pub struct ScreenReaderBridge(Box<dyn ScreenReader>);
+
+impl ScreenReaderBridge {
+    pub fn new(reader: impl ScreenReader) -> Self {
+        Self(Box::new(reader))
+    }
+
+    /// Picks the stand-in reader from `MINESWEEPER_SCREEN_READER` (`stdout` or `log`).
+    pub fn from_env() -> Self {
+        match std::env::var("MINESWEEPER_SCREEN_READER").as_deref() {
+            Ok("stdout") => Self::new(StdoutScreenReader),
+            _ => Self::new(LogScreenReader),
+        }
+    }
+
+    pub fn announce(&mut self, message: impl AsRef<str>) {
+        self.0.announce(message.as_ref());
+    }
+}
+
+#[derive(Resource)]
+pub struct AudioCues {
+    pub numbers: Vec<Handle<Pitch>>,
+    pub blank: Handle<Pitch>,
+    pub hidden: Handle<Pitch>,
+    pub flag: Handle<Pitch>,
+    pub mine: Handle<Pitch>,
+}
@@ -0,0 +1,269 @@
+use crate::{
+    accessibility::{
+        AccessibilitySettings, AudioCues, CursorHighlight, KeyboardCursor, ScreenReaderBridge,
+    },
+    board::{BoardSettings, TileImages},
+    game::{GameStats, Lives, events::*},
+    input::systems::click_tile,
+    sound::{AudioSettings, play_sound},
+    tile::{Position, Tile, TileSprite},
+    utils::{tile_flipped, tile_sprite_size, tile_translation},
+};
+use bevy::prelude::*;
+use std::time::Duration;
+
+pub fn accessibility_enabled(settings: Res<AccessibilitySettings>) -> bool {
+    settings.enabled
+}
+
+pub fn setup_audio_cues(mut commands: Commands, mut pitches: ResMut<Assets<Pitch>>) {
+    let short = Duration::from_millis(120);
+
+    let numbers = (1..=8)
+        .map(|count| pitches.add(Pitch::new(330.0 + count as f32 * 110.0, short)))
+        .collect();
+
+    commands.insert_resource(AudioCues {
+        numbers,
+        blank: pitches.add(Pitch::new(262.0, short)),
+        hidden: pitches.add(Pitch::new(196.0, Duration::from_millis(60))),
+        flag: pitches.add(Pitch::new(1568.0, Duration::from_millis(80))),
+        mine: pitches.add(Pitch::new(82.0, Duration::from_millis(400))),
+    });
+}
+
+pub fn setup_cursor_highlight(mut commands: Commands, settings: Res<BoardSettings>) {
+    commands.spawn((
+        Sprite::from_color(
+            Color::srgba(0.2, 0.6, 1.0, 0.45),
+            Vec2::splat(settings.tile_size),
+        ),
+        Transform::from_translation(Vec3::new(0.0, 0.0, 1.0)),
+        Visibility::Hidden,
+        CursorHighlight,
+    ));
+}
+
+pub fn toggle_accessibility(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<AccessibilitySettings>,
+    mut screen_reader: ResMut<ScreenReaderBridge>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F1) {
+        settings.enabled = !settings.enabled;
+
+        screen_reader.announce(if settings.enabled {
+            "Accessibility mode on. Arrow keys move, I repeats, space reveals, F flags, R restarts."
+        } else {
+            "Accessibility mode off."
+        });
+    }
+}
+
+/// Keeps the cursor on the board when a new one is smaller than the last.
+pub fn clamp_cursor(settings: Res<BoardSettings>, mut cursor: ResMut<KeyboardCursor>) {
+    let position = Position {
+        x: cursor.position.x.min(settings.width.saturating_sub(1)),
+        y: cursor.position.y.min(settings.height.saturating_sub(1)),
+    };
+    if position != cursor.position {
+        cursor.position = position;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle_cursor_movement(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    settings: Res<BoardSettings>,
+    cues: Res<AudioCues>,
+    audio_settings: Res<AudioSettings>,
+    mut cursor: ResMut<KeyboardCursor>,
+    mut screen_reader: ResMut<ScreenReaderBridge>,
+    tile_query: Query<(&Position, &Tile), With<TileSprite>>,
+) {
+    let mut position = cursor.position;
+
+    if keyboard_input.just_pressed(KeyCode::ArrowUp) && position.y + 1 < settings.height {
+        position.y += 1;
+    }
+    if keyboard_input.just_pressed(KeyCode::ArrowDown) && position.y > 0 {
+        position.y -= 1;
+    }
+    if keyboard_input.just_pressed(KeyCode::ArrowRight) && position.x + 1 < settings.width {
+        position.x += 1;
+    }
+    if keyboard_input.just_pressed(KeyCode::ArrowLeft) && position.x > 0 {
+        position.x -= 1;
+    }
+
+    if position == cursor.position && !keyboard_input.just_pressed(KeyCode::KeyI) {
+        return;
+    }
+    cursor.position = position;
+
+    if let Some((_, tile)) = tile_query.iter().find(|(pos, _)| **pos == position) {
+        screen_reader.announce(describe_tile(position, tile, &settings));
+        play_cue(&mut commands, &cues, tile, &audio_settings);
+    } else {
+        let row = settings.height.saturating_sub(position.y);
+        let column = position.x + 1;
+        screen_reader.announce(format!("row {row} column {column}, outside the board"));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle_cursor_action(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    settings: Res<BoardSettings>,
+    cursor: Res<KeyboardCursor>,
+    tile_query: Query<(&Position, &Tile), With<TileSprite>>,
+    mut game_stats: ResMut<GameStats>,
+    mut tile_flagged_events: EventWriter<TileFlaggedEvent>,
+    mut tile_revealed_events: EventWriter<TileRevealedEvent>,
+    mut chord_events: EventWriter<ChordEvent>,
+) {
+    let position = cursor.position;
+
+    let left_click = if keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::Enter]) {
+        true
+    } else if keyboard_input.just_pressed(KeyCode::KeyF) {
+        false
+    } else {
+        return;
+    };
+
+    let Some((_, tile)) = tile_query.iter().find(|(pos, _)| **pos == position) else {
+        return;
+    };
+    let tile = tile.clone();
+
+    click_tile(
+        position,
+        &tile,
+        left_click,
+        &tile_query,
+        &settings,
+        &mut game_stats,
+        &mut tile_flagged_events,
+        &mut tile_revealed_events,
+        &mut chord_events,
+    );
+}
+
+#[allow(clippy::type_complexity)]
+pub fn announce_tile_changes(
+    mut commands: Commands,
+    settings: Res<BoardSettings>,
+    cues: Res<AudioCues>,
+    audio_settings: Res<AudioSettings>,
+    cursor: Res<KeyboardCursor>,
+    mut screen_reader: ResMut<ScreenReaderBridge>,
+    tile_query: Query<(&Position, &Tile), (With<TileSprite>, Changed<Tile>)>,
+) {
+    if let Some((_, tile)) = tile_query.iter().find(|(pos, _)| **pos == cursor.position) {
+        screen_reader.announce(describe_tile(cursor.position, tile, &settings));
+        play_cue(&mut commands, &cues, tile, &audio_settings);
+    }
+}
+
+pub fn announce_game_end(
+    lives: Res<Lives>,
+    mut screen_reader: ResMut<ScreenReaderBridge>,
+    mut mine_hit_events: EventReader<MineHitEvent>,
+    mut game_over_events: EventReader<GameOverEvent>,
+    mut game_won_events: EventReader<GameWonEvent>,
+) {
+    for _ in mine_hit_events.read() {
+        screen_reader.announce(match lives.remaining {
+            0 => "Mine hit.".to_string(),
+            1 => "Mine hit. 1 life left.".to_string(),
+            remaining => format!("Mine hit. {remaining} lives left."),
+        });
+    }
+    for _ in game_over_events.read() {
+        screen_reader.announce("Mine hit. Game over. Press R to restart.");
+    }
+    for _ in game_won_events.read() {
+        screen_reader.announce("Board cleared. You won. Press R to restart.");
+    }
+}
+
+pub fn update_cursor_highlight(
+    settings: Res<BoardSettings>,
+    tile_images: Res<TileImages>,
+    accessibility: Res<AccessibilitySettings>,
+    cursor: Res<KeyboardCursor>,
+    mut highlight: Single<(&mut Transform, &mut Sprite, &mut Visibility), With<CursorHighlight>>,
+) {
+    let (transform, sprite, visibility) = &mut *highlight;
+
+    **visibility = if accessibility.enabled {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    let translation = tile_translation(cursor.position, &settings);
+    sprite.custom_size = Some(tile_sprite_size(&settings));
+    sprite.flip_y = tile_flipped(cursor.position, &settings);
+    let image = tile_images.get(settings.shape);
+    if sprite.image != image {
+        sprite.image = image;
+    }
+    transform.translation.x = translation.x;
+    transform.translation.y = translation.y;
+}
+
+pub fn describe_tile(position: Position, tile: &Tile, settings: &BoardSettings) -> String {
+    let row = settings.height.saturating_sub(position.y);
+    let column = position.x + 1;
+
+    let state = if tile.is_wrong_flag() {
+        "flagged, no mine".to_string()
+    } else if tile.is_revealed {
+        if tile.is_exploded {
+            "revealed, exploded mine".to_string()
+        } else if tile.mines > 1 {
+            format!("revealed, {} mines", tile.mines)
+        } else if tile.is_mine() {
+            "revealed, mine".to_string()
+        } else if tile.adjacent_mines == 0 {
+            "revealed, blank".to_string()
+        } else {
+            format!("revealed, {}", tile.adjacent_mines)
+        }
+    } else if tile.flags > 1 {
+        format!("flagged {} times", tile.flags)
+    } else if tile.is_flagged() {
+        "flagged".to_string()
+    } else {
+        "hidden".to_string()
+    };
+
+    format!("row {row} column {column}, {state}")
+}
+
+fn play_cue(
+    commands: &mut Commands,
+    cues: &AudioCues,
+    tile: &Tile,
+    audio_settings: &AudioSettings,
+) {
+    let cue = if tile.is_wrong_flag() {
+        &cues.flag
+    } else if tile.is_revealed {
+        if tile.is_mine() {
+            &cues.mine
+        } else if tile.adjacent_mines == 0 {
+            &cues.blank
+        } else {
+            &cues.numbers[(tile.adjacent_mines as usize).min(cues.numbers.len()) - 1]
+        }
+    } else if tile.is_flagged() {
+        &cues.flag
+    } else {
+        &cues.hidden
+    };
+
+    play_sound(commands, cue, 0.5, audio_settings);
+}
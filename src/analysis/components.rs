@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct AnalysisText;
+
+#[derive(Component)]
+pub struct AnalysisMarker;
@@ -0,0 +1,29 @@
+pub mod components;
+pub mod resources;
+pub mod review;
+pub mod systems;
+
+use bevy::prelude::*;
+pub use components::*;
+pub use resources::*;
+use systems::*;
+
+pub struct AnalysisPlugin;
+
+impl Plugin for AnalysisPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Analysis>()
+            .add_systems(Startup, spawn_analysis_text)
+            .add_systems(
+                Update,
+                (
+                    capture_start,
+                    toggle_analysis,
+                    step_analysis,
+                    show_analysis_step.run_if(resource_changed::<Analysis>),
+                    update_analysis_text,
+                )
+                    .chain(),
+            );
+    }
+}
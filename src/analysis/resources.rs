@@ -0,0 +1,41 @@
+use crate::{
+    analysis::review::{ReviewedMove, Verdict},
+    board::layout::BoardLayout,
+    tile::Tile,
+};
+use bevy::prelude::*;
+
+/// The review of the last game, stepped through one move at a time once it is over.
+#[derive(Resource, Default)]
+pub struct Analysis {
+    /// The board as dealt, captured when its tiles appear.
+    pub start: Option<BoardLayout>,
+    pub moves: Vec<ReviewedMove>,
+    pub step: usize,
+    pub open: bool,
+    /// The tiles as the game left them, put back when the review closes.
+    pub final_tiles: Vec<(Entity, Tile)>,
+}
+
+impl Analysis {
+    pub fn current(&self) -> Option<&ReviewedMove> {
+        self.open.then(|| self.moves.get(self.step)).flatten()
+    }
+
+    /// The nearest move in the given direction that was not forced.
+    pub fn next_lesson(&self, forward: bool) -> Option<usize> {
+        let is_lesson = |step: &usize| self.moves[*step].verdict != Verdict::ForcedSafe;
+        if forward {
+            (self.step + 1..self.moves.len()).find(is_lesson)
+        } else {
+            (0..self.step).rev().find(is_lesson)
+        }
+    }
+
+    pub fn count(&self, verdict: Verdict) -> usize {
+        self.moves
+            .iter()
+            .filter(|reviewed| reviewed.verdict == verdict)
+            .count()
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{
+    board::layout::BoardLayout,
+    solver::{Cell, SolverBoard},
+};
+
+/// A player's move, by cell index.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Move {
+    Reveal(usize),
+    Chord(usize),
+    Flag(usize),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Verdict {
+    /// Every cell the move opened was provably safe.
+    ForcedSafe,
+    /// Nothing was provable, and the guess came off.
+    LuckyGuess,
+    /// The move guessed although some cell was provably safe.
+    UnnecessaryGuess,
+    FatalMistake,
+}
+
+impl Verdict {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Verdict::ForcedSafe => "Forced safe",
+            Verdict::LuckyGuess => "Lucky guess",
+            Verdict::UnnecessaryGuess => "Unnecessary guess",
+            Verdict::FatalMistake => "Fatal mistake",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ReviewedMove {
+    pub cell: usize,
+    /// The hidden cells the move opened, before any blank cascade.
+    pub opened: Vec<usize>,
+    pub verdict: Verdict,
+    /// What could be proved from the board just before the move.
+    pub safe: Vec<usize>,
+    pub mines: Vec<usize>,
+    /// The board as the player saw it just before the move.
+    pub revealed: Vec<bool>,
+    pub flags: Vec<u8>,
+}
+
+impl ReviewedMove {
+    /// Cells worth a look before making this move: the provably safe ones when it guessed,
+    /// and the proven mines it walked into.
+    pub fn considered(&self) -> Vec<usize> {
+        match self.verdict {
+            Verdict::ForcedSafe | Verdict::LuckyGuess => Vec::new(),
+            Verdict::UnnecessaryGuess => self.safe.clone(),
+            Verdict::FatalMistake => self
+                .safe
+                .iter()
+                .chain(self.opened.iter().filter(|cell| self.mines.contains(cell)))
+                .copied()
+                .collect(),
+        }
+    }
+}
+
+/// Replays `moves` on the board `start` shows and judges each one that opens cells against
+/// what the solver could prove at that point. Flags are played but not judged.
+pub fn review_game(
+    start: &BoardLayout,
+    neighbours: &[Vec<usize>],
+    max_mines_per_cell: u8,
+    moves: &[Move],
+) -> Vec<ReviewedMove> {
+    let mut board = Playthrough {
+        layout: start,
+        neighbours,
+        revealed: start.revealed.clone(),
+        flags: start.flags.clone(),
+    };
+    let mut reviewed = Vec::new();
+
+    for action in moves {
+        let (cell, opened) = match *action {
+            Move::Flag(cell) => {
+                if !board.revealed[cell] {
+                    board.flags[cell] = (board.flags[cell] + 1) % (max_mines_per_cell + 1);
+                }
+                continue;
+            }
+            Move::Reveal(cell) => (cell, vec![cell]),
+            Move::Chord(cell) => (cell, neighbours[cell].clone()),
+        };
+        let opened: Vec<usize> = opened
+            .into_iter()
+            .filter(|cell| board.can_open(*cell))
+            .collect();
+        if opened.is_empty() {
+            continue;
+        }
+
+        let deductions = board.visible(max_mines_per_cell).deduce();
+        let verdict = if opened.iter().any(|cell| start.mines[*cell] > 0) {
+            Verdict::FatalMistake
+        } else if opened.iter().all(|cell| deductions.safe.contains(cell)) {
+            Verdict::ForcedSafe
+        } else if !deductions.safe.is_empty() {
+            Verdict::UnnecessaryGuess
+        } else {
+            Verdict::LuckyGuess
+        };
+
+        reviewed.push(ReviewedMove {
+            cell,
+            opened: opened.clone(),
+            verdict,
+            safe: deductions.safe,
+            mines: deductions.mines.iter().map(|(cell, _)| *cell).collect(),
+            revealed: board.revealed.clone(),
+            flags: board.flags.clone(),
+        });
+
+        for cell in opened {
+            board.open(cell);
+        }
+    }
+
+    reviewed
+}
+
+/// The board as play goes on, following the game's rules for opening cells.
+struct Playthrough<'a> {
+    layout: &'a BoardLayout,
+    neighbours: &'a [Vec<usize>],
+    revealed: Vec<bool>,
+    flags: Vec<u8>,
+}
+
+impl Playthrough<'_> {
+    fn can_open(&self, cell: usize) -> bool {
+        self.layout.has_cell(cell) && !self.revealed[cell] && self.flags[cell] == 0
+    }
+
+    fn number(&self, cell: usize) -> u8 {
+        self.neighbours[cell]
+            .iter()
+            .map(|neighbour| self.layout.mines[*neighbour])
+            .sum()
+    }
+
+    fn open(&mut self, cell: usize) {
+        self.revealed[cell] = true;
+        if self.layout.mines[cell] > 0 {
+            // A mine hit with lives to spare stays flagged on the board.
+            self.flags[cell] = self.layout.mines[cell];
+            return;
+        }
+
+        let mut to_open = vec![cell];
+        while let Some(cell) = to_open.pop() {
+            if self.number(cell) > 0 {
+                continue;
+            }
+            for neighbour in &self.neighbours[cell] {
+                if self.can_open(*neighbour) && self.layout.mines[*neighbour] == 0 {
+                    self.revealed[*neighbour] = true;
+                    to_open.push(*neighbour);
+                }
+            }
+        }
+    }
+
+    fn visible(&self, max_mines_per_cell: u8) -> SolverBoard {
+        SolverBoard {
+            cells: (0..self.revealed.len())
+                .map(|cell| match cell {
+                    _ if !self.layout.has_cell(cell) => Cell::Missing,
+                    _ if !self.revealed[cell] => Cell::Hidden,
+                    _ if self.layout.mines[cell] > 0 => Cell::Mine(self.layout.mines[cell]),
+                    _ => Cell::Revealed(self.number(cell)),
+                })
+                .collect(),
+            neighbours: self.neighbours.to_vec(),
+            max_mines_per_cell,
+            total_mines: self.layout.mine_count() as u32,
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use crate::{
+    analysis::{
+        review::{Move, Verdict, review_game},
+        *,
+    },
+    board::{
+        BoardSettings, TileImages,
+        grid::{neighbour_table, tile_index, tile_position},
+        systems::current_layout,
+    },
+    game::{ClickKind, GameState, GameStats, components::OverlayText},
+    tile::{Position, Tile, TileSprite},
+    transfer::TransferStatus,
+    utils::{tile_flipped, tile_sprite_size, tile_translation},
+};
+use bevy::prelude::*;
+
+const ANALYSIS_FONT_SIZE: f32 = 14.0;
+
+pub fn spawn_analysis_text(mut commands: Commands) {
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font_size: ANALYSIS_FONT_SIZE,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(6.0),
+            left: Val::Px(8.0),
+            padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0., 0., 0., 0.6)),
+        Visibility::Hidden,
+        AnalysisText,
+    ));
+}
+
+/// Keeps the board as dealt so the game can be played back move by move later.
+pub fn capture_start(
+    settings: Res<BoardSettings>,
+    new_tiles: Query<(), Added<TileSprite>>,
+    tile_query: Query<(&Position, &Tile), With<TileSprite>>,
+    mut analysis: ResMut<Analysis>,
+) {
+    if new_tiles.is_empty() {
+        return;
+    }
+
+    let mut layout = current_layout(&settings, tile_query.iter());
+    for (position, tile) in tile_query.iter() {
+        let index = tile_index(*position, &settings);
+        layout.revealed[index] = tile.is_revealed;
+        layout.flags[index] = tile.flags;
+    }
+    *analysis = Analysis {
+        start: Some(layout),
+        ..default()
+    };
+}
+
+pub fn toggle_analysis(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    state: Res<State<GameState>>,
+    settings: Res<BoardSettings>,
+    game_stats: Res<GameStats>,
+    mut analysis: ResMut<Analysis>,
+    mut tile_query: Query<(Entity, &mut Tile), With<TileSprite>>,
+    mut status: ResMut<TransferStatus>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyA) {
+        return;
+    }
+
+    if analysis.open {
+        for (entity, tile) in std::mem::take(&mut analysis.final_tiles) {
+            if let Ok((_, mut current)) = tile_query.get_mut(entity) {
+                *current = tile;
+            }
+        }
+        analysis.open = false;
+        return;
+    }
+
+    if !matches!(state.get(), GameState::Won | GameState::GameOver) {
+        return;
+    }
+    let Some(start) = &analysis.start else {
+        return;
+    };
+
+    let moves: Vec<Move> = game_stats
+        .click_log
+        .iter()
+        .filter(|click| !click.wasted)
+        .map(|click| {
+            let cell = tile_index(click.position, &settings);
+            match click.kind {
+                ClickKind::Left => Move::Reveal(cell),
+                ClickKind::Chord => Move::Chord(cell),
+                ClickKind::Right => Move::Flag(cell),
+            }
+        })
+        .collect();
+    let reviewed = review_game(
+        start,
+        &neighbour_table(&settings),
+        settings.max_mines_per_cell(),
+        &moves,
+    );
+    if reviewed.is_empty() {
+        status.show("No moves to analyse".to_string());
+        return;
+    }
+
+    analysis.final_tiles = tile_query
+        .iter()
+        .map(|(entity, tile)| (entity, tile.clone()))
+        .collect();
+    analysis.moves = reviewed;
+    analysis.step = 0;
+    analysis.open = true;
+}
+
+/// Left and right step through the moves, up and down jump between the ones worth a lesson.
+pub fn step_analysis(keyboard_input: Res<ButtonInput<KeyCode>>, mut analysis: ResMut<Analysis>) {
+    if !analysis.open {
+        return;
+    }
+
+    let step = if keyboard_input.just_pressed(KeyCode::ArrowRight) {
+        Some((analysis.step + 1).min(analysis.moves.len() - 1))
+    } else if keyboard_input.just_pressed(KeyCode::ArrowLeft) {
+        Some(analysis.step.saturating_sub(1))
+    } else if keyboard_input.just_pressed(KeyCode::ArrowDown) {
+        analysis.next_lesson(true)
+    } else if keyboard_input.just_pressed(KeyCode::ArrowUp) {
+        analysis.next_lesson(false)
+    } else {
+        None
+    };
+
+    if let Some(step) = step
+        && step != analysis.step
+    {
+        analysis.step = step;
+    }
+}
+
+fn verdict_color(verdict: Verdict) -> Color {
+    match verdict {
+        Verdict::ForcedSafe => Color::srgba(0.2, 0.6, 1.0, 0.55),
+        Verdict::LuckyGuess => Color::srgba(1.0, 0.85, 0.2, 0.55),
+        Verdict::UnnecessaryGuess => Color::srgba(1.0, 0.5, 0.1, 0.55),
+        Verdict::FatalMistake => Color::srgba(0.9, 0.1, 0.1, 0.6),
+    }
+}
+
+const CONSIDERED_COLOR: Color = Color::srgba(0.3, 0.9, 0.4, 0.45);
+
+/// Puts the board back to how it looked before the current move and marks the move and the
+/// cells worth considering instead.
+pub fn show_analysis_step(
+    mut commands: Commands,
+    settings: Res<BoardSettings>,
+    tile_images: Res<TileImages>,
+    analysis: Res<Analysis>,
+    mut tile_query: Query<(&Position, &mut Tile), With<TileSprite>>,
+    mut overlay_query: Query<&mut Visibility, With<OverlayText>>,
+    markers: Query<Entity, With<AnalysisMarker>>,
+) {
+    for entity in markers.iter() {
+        commands.entity(entity).despawn();
+    }
+    for mut visibility in overlay_query.iter_mut() {
+        visibility.set_if_neq(if analysis.open {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        });
+    }
+
+    let Some(reviewed) = analysis.current() else {
+        return;
+    };
+
+    for (position, mut tile) in tile_query.iter_mut() {
+        let index = tile_index(*position, &settings);
+        let revealed = reviewed.revealed[index];
+        let flags = reviewed.flags[index];
+        let exploded = revealed && tile.is_mine();
+        if (tile.is_revealed, tile.flags, tile.is_exploded) != (revealed, flags, exploded) {
+            tile.is_revealed = revealed;
+            tile.flags = flags;
+            tile.is_exploded = exploded;
+        }
+    }
+
+    let considered = reviewed.considered();
+    let marked = std::iter::once((reviewed.cell, verdict_color(reviewed.verdict))).chain(
+        considered
+            .iter()
+            .filter(|cell| **cell != reviewed.cell)
+            .map(|cell| (*cell, CONSIDERED_COLOR)),
+    );
+    for (cell, color) in marked {
+        let position = tile_position(cell, &settings);
+        let translation = tile_translation(position, &settings);
+        commands.spawn((
+            Sprite {
+                image: tile_images.get(settings.shape),
+                color,
+                custom_size: Some(tile_sprite_size(&settings)),
+                flip_y: tile_flipped(position, &settings),
+                ..default()
+            },
+            Transform::from_translation(translation.extend(1.5)),
+            AnalysisMarker,
+        ));
+    }
+}
+
+pub fn update_analysis_text(
+    state: Res<State<GameState>>,
+    settings: Res<BoardSettings>,
+    analysis: Res<Analysis>,
+    mut text: Single<(&mut Text, &mut Visibility), With<AnalysisText>>,
+) {
+    let (text, visibility) = &mut *text;
+    let content = match analysis.current() {
+        Some(reviewed) => {
+            let position = tile_position(reviewed.cell, &settings);
+            let detail = match reviewed.verdict {
+                Verdict::ForcedSafe => "the numbers proved it safe".to_string(),
+                Verdict::LuckyGuess => "nothing could be proved here".to_string(),
+                Verdict::UnnecessaryGuess => {
+                    format!("{} cells were provably safe", reviewed.safe.len())
+                }
+                Verdict::FatalMistake if reviewed.mines.contains(&reviewed.cell) => {
+                    "this cell was provably a mine".to_string()
+                }
+                Verdict::FatalMistake if !reviewed.safe.is_empty() => {
+                    format!("{} cells were provably safe", reviewed.safe.len())
+                }
+                Verdict::FatalMistake => "a forced guess that failed".to_string(),
+            };
+            format!(
+                "Move {} of {}: {} at column {}, row {}\n{detail}\n\
+                 Forced {}  Lucky {}  Unnecessary {}  Fatal {}\n\
+                 Left/Right step  Up/Down next lesson  A close",
+                analysis.step + 1,
+                analysis.moves.len(),
+                reviewed.verdict.label(),
+                position.x + 1,
+                settings.height - position.y,
+                analysis.count(Verdict::ForcedSafe),
+                analysis.count(Verdict::LuckyGuess),
+                analysis.count(Verdict::UnnecessaryGuess),
+                analysis.count(Verdict::FatalMistake),
+            )
+        }
+        None if matches!(state.get(), GameState::Won | GameState::GameOver) => {
+            "A: analyse this game".to_string()
+        }
+        None => String::new(),
+    };
+
+    let shown = if content.is_empty() {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+    visibility.set_if_neq(shown);
+    if text.0 != content {
+        text.0 = content;
+    }
+}
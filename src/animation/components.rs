@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TileAnimationKind {
+    Flip,
+    FlagDrop,
+    Explode,
+}
+
+impl TileAnimationKind {
+    pub fn duration(&self) -> f32 {
+        match self {
+            TileAnimationKind::Flip => 0.15,
+            TileAnimationKind::FlagDrop => 0.2,
+            TileAnimationKind::Explode => 0.35,
+        }
+    }
+}
+
+#[derive(Component, Debug, Default)]
+pub struct TileAnimation {
+    pub kind: Option<TileAnimationKind>,
+    pub delay: Timer,
+    pub progress: Timer,
+    pub target: Color,
+}
+
+impl TileAnimation {
+    pub fn start(&mut self, kind: TileAnimationKind, target: Color, delay: f32) {
+        self.kind = Some(kind);
+        self.target = target;
+        self.delay = Timer::from_seconds(delay, TimerMode::Once);
+        self.progress = Timer::from_seconds(kind.duration(), TimerMode::Once);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.kind.is_some()
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Confetti {
+    pub velocity: Vec2,
+    pub spin: f32,
+    pub lifetime: Timer,
+}
@@ -0,0 +1,37 @@
+pub mod components;
+pub mod resources;
+pub mod systems;
+
+use crate::{game::events::NewGameEvent, tile::systems::render_tiles};
+use bevy::prelude::*;
+pub use components::*;
+pub use resources::*;
+use systems::*;
+
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AnimationSettings::load())
+            .init_resource::<LastReveal>()
+            .add_systems(
+                Update,
+                (
+                    clear_confetti.run_if(on_event::<NewGameEvent>),
+                    save_animation_settings.run_if(resource_changed::<AnimationSettings>),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    skip_animations,
+                    queue_tile_animations.run_if(animations_enabled),
+                    celebrate_win.run_if(animations_enabled),
+                    animate_tiles,
+                    animate_confetti,
+                )
+                    .chain()
+                    .before(render_tiles),
+            );
+    }
+}
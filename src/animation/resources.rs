@@ -0,0 +1,35 @@
+use crate::{
+    storage::{load_json, save_json},
+    tile::Position,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnimationSettings {
+    pub enabled: bool,
+}
+
+impl Default for AnimationSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+impl AnimationSettings {
+    const FILE_NAME: &'static str = "animation.json";
+
+    pub fn load() -> Self {
+        load_json(Self::FILE_NAME)
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = save_json(Self::FILE_NAME, self) {
+            warn!("Failed to save animation settings: {err}");
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct LastReveal(pub Position);
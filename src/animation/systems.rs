@@ -0,0 +1,293 @@
+use crate::{
+    animation::{AnimationSettings, Confetti, LastReveal, TileAnimation, TileAnimationKind},
+    board::{BoardSettings, grid::neighbours},
+    game::events::*,
+    tile::{Position, Tile, TileSprite, systems::tile_color},
+    utils::{board_extent, tile_translation},
+};
+use bevy::prelude::*;
+use rand::{prelude::*, rng};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+const RIPPLE_STEP: f32 = 0.03;
+const CHAIN_STEP: f32 = 0.06;
+const FLAG_ALL_STEP: f32 = 0.02;
+const CONFETTI_COUNT: usize = 120;
+const CONFETTI_GRAVITY: f32 = 400.0;
+
+pub fn animations_enabled(settings: Res<AnimationSettings>) -> bool {
+    settings.enabled
+}
+
+#[allow(clippy::type_complexity)]
+pub fn queue_tile_animations(
+    settings: Res<BoardSettings>,
+    mut last_reveal: ResMut<LastReveal>,
+    mut tile_revealed_events: EventReader<TileRevealedEvent>,
+    mut tile_query: Query<
+        (&Position, &Tile, &mut Sprite, &mut TileAnimation),
+        (With<TileSprite>, Changed<Tile>),
+    >,
+) {
+    let mut origins: Vec<Position> = tile_revealed_events
+        .read()
+        .map(|event| event.position)
+        .collect();
+    match origins.last() {
+        Some(position) => last_reveal.0 = *position,
+        None => origins.push(last_reveal.0),
+    }
+
+    let opened: HashSet<Position> = tile_query
+        .iter()
+        .filter(|(_, tile, ..)| tile.is_revealed && !tile.is_mine() && !tile.is_flagged())
+        .map(|(position, ..)| *position)
+        .collect();
+    let distances = ripple_distances(&origins, &opened, &settings);
+    let mut flags_dropped = 0;
+
+    for (position, tile, mut sprite, mut animation) in tile_query.iter_mut() {
+        let target = tile_color(tile);
+        if sprite.color == target && !animation.is_active() {
+            continue;
+        }
+
+        if tile.is_revealed && (tile.is_mine() || tile.is_flagged()) {
+            let distance = Vec2::new(
+                position.x as f32 - last_reveal.0.x as f32,
+                position.y as f32 - last_reveal.0.y as f32,
+            )
+            .length();
+            let kind = if tile.is_mine() {
+                TileAnimationKind::Explode
+            } else {
+                TileAnimationKind::Flip
+            };
+            animation.start(kind, target, distance * CHAIN_STEP);
+        } else if tile.is_revealed {
+            let distance = distances.get(position).copied().unwrap_or(0);
+            animation.start(
+                TileAnimationKind::Flip,
+                target,
+                distance as f32 * RIPPLE_STEP,
+            );
+        } else if tile.is_flagged() {
+            let delay = flags_dropped as f32 * FLAG_ALL_STEP;
+            animation.start(TileAnimationKind::FlagDrop, target, delay);
+            flags_dropped += 1;
+        } else {
+            animation.kind = None;
+            sprite.color = target;
+        }
+    }
+}
+
+fn ripple_distances(
+    origins: &[Position],
+    opened: &HashSet<Position>,
+    settings: &BoardSettings,
+) -> HashMap<Position, u32> {
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for origin in origins {
+        if opened.contains(origin) && !distances.contains_key(origin) {
+            distances.insert(*origin, 0);
+            queue.push_back(*origin);
+        }
+    }
+
+    while let Some(position) = queue.pop_front() {
+        let distance = distances[&position];
+
+        for adjacent_pos in neighbours(position, settings) {
+            if opened.contains(&adjacent_pos) && !distances.contains_key(&adjacent_pos) {
+                distances.insert(adjacent_pos, distance + 1);
+                queue.push_back(adjacent_pos);
+            }
+        }
+    }
+
+    for position in opened {
+        distances.entry(*position).or_insert_with(|| {
+            origins
+                .iter()
+                .map(|origin| {
+                    (position.x.abs_diff(origin.x)).max(position.y.abs_diff(origin.y)) as u32
+                })
+                .min()
+                .unwrap_or(0)
+        });
+    }
+
+    distances
+}
+
+pub fn celebrate_win(
+    mut commands: Commands,
+    settings: Res<BoardSettings>,
+    mut game_won_events: EventReader<GameWonEvent>,
+) {
+    if game_won_events.read().count() == 0 {
+        return;
+    }
+
+    let mut rng = rng();
+    let half_extent = board_extent(&settings) * settings.tile_size / 2.;
+    let (half_width, top) = (half_extent.x, half_extent.y);
+    let palette = [
+        Color::srgb(1.0, 0.3, 0.3),
+        Color::srgb(0.3, 0.8, 0.3),
+        Color::srgb(0.3, 0.5, 1.0),
+        Color::srgb(1.0, 0.85, 0.2),
+        Color::srgb(0.9, 0.4, 0.9),
+    ];
+
+    for _ in 0..CONFETTI_COUNT {
+        commands.spawn((
+            Sprite::from_color(*palette.choose(&mut rng).unwrap(), Vec2::new(6.0, 10.0)),
+            Transform::from_translation(Vec3::new(
+                rng.random_range(-half_width..half_width),
+                top + rng.random_range(0.0..40.0),
+                5.0,
+            )),
+            Confetti {
+                velocity: Vec2::new(
+                    rng.random_range(-80.0..80.0),
+                    rng.random_range(-50.0..150.0),
+                ),
+                spin: rng.random_range(-6.0..6.0),
+                lifetime: Timer::from_seconds(2.5, TimerMode::Once),
+            },
+        ));
+    }
+}
+
+pub fn animate_tiles(
+    time: Res<Time>,
+    settings: Res<BoardSettings>,
+    mut tile_query: Query<
+        (&Position, &mut TileAnimation, &mut Sprite, &mut Transform),
+        With<TileSprite>,
+    >,
+) {
+    for (position, mut animation, mut sprite, mut transform) in tile_query.iter_mut() {
+        let Some(kind) = animation.kind else {
+            continue;
+        };
+        if !animation.delay.tick(time.delta()).finished() {
+            continue;
+        }
+
+        let t = animation.progress.tick(time.delta()).fraction();
+        if animation.progress.finished() {
+            finish_animation(
+                *position,
+                &mut animation,
+                &mut sprite,
+                &mut transform,
+                &settings,
+            );
+            continue;
+        }
+
+        match kind {
+            TileAnimationKind::Flip => {
+                transform.scale.x = (1.0 - 2.0 * t).abs();
+                if t >= 0.5 {
+                    sprite.color = animation.target;
+                }
+            }
+            TileAnimationKind::FlagDrop => {
+                let base_y = tile_translation(*position, &settings).y;
+                sprite.color = animation.target;
+                transform.translation.y = base_y + (1.0 - t).powi(2) * settings.tile_size * 0.6;
+                transform.scale = Vec3::splat(1.0 + 0.3 * (1.0 - t));
+            }
+            TileAnimationKind::Explode => {
+                sprite.color = animation.target;
+                transform.scale = Vec3::splat(1.0 + 0.6 * (1.0 - t));
+            }
+        }
+    }
+}
+
+fn finish_animation(
+    position: Position,
+    animation: &mut TileAnimation,
+    sprite: &mut Sprite,
+    transform: &mut Transform,
+    settings: &BoardSettings,
+) {
+    animation.kind = None;
+    sprite.color = animation.target;
+    transform.scale = Vec3::ONE;
+    let translation = tile_translation(position, settings);
+    transform.translation.x = translation.x;
+    transform.translation.y = translation.y;
+}
+
+pub fn skip_animations(
+    mut commands: Commands,
+    settings: Res<BoardSettings>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut tile_query: Query<
+        (&Position, &mut TileAnimation, &mut Sprite, &mut Transform),
+        With<TileSprite>,
+    >,
+    confetti_query: Query<Entity, With<Confetti>>,
+) {
+    if keyboard_input.get_just_pressed().next().is_none()
+        && mouse_input.get_just_pressed().next().is_none()
+    {
+        return;
+    }
+
+    for (position, mut animation, mut sprite, mut transform) in tile_query.iter_mut() {
+        if animation.is_active() {
+            finish_animation(
+                *position,
+                &mut animation,
+                &mut sprite,
+                &mut transform,
+                &settings,
+            );
+        }
+    }
+
+    for entity in confetti_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn animate_confetti(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut confetti_query: Query<(Entity, &mut Confetti, &mut Transform)>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, mut confetti, mut transform) in confetti_query.iter_mut() {
+        if confetti.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        confetti.velocity.y -= CONFETTI_GRAVITY * delta;
+        transform.translation += (confetti.velocity * delta).extend(0.0);
+        transform.rotate_z(confetti.spin * delta);
+    }
+}
+
+pub fn clear_confetti(mut commands: Commands, confetti_query: Query<Entity, With<Confetti>>) {
+    for entity in confetti_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn save_animation_settings(animation_settings: Res<AnimationSettings>) {
+    if !animation_settings.is_added() {
+        animation_settings.save();
+    }
+}
@@ -0,0 +1,40 @@
+pub mod components;
+pub mod resources;
+pub mod systems;
+
+use crate::{
+    game::{GameState, systems::handle_tile_revealed},
+    mode::systems::start_run,
+    net::playing_online,
+    replay::replay_running,
+};
+use bevy::prelude::*;
+pub use components::*;
+pub use resources::*;
+use systems::*;
+
+pub struct AutoplayPlugin;
+
+impl Plugin for AutoplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AutoplaySettings::load())
+            .init_resource::<Autoplay>()
+            .add_systems(Startup, spawn_autoplay_text)
+            .add_systems(
+                Update,
+                (
+                    toggle_autoplay.run_if(not(playing_online)),
+                    start_attract_mode.run_if(not(playing_online)),
+                    deal_next_demo_board.before(start_run),
+                    run_autoplay
+                        .run_if(in_state(GameState::Playing))
+                        .run_if(not(replay_running))
+                        .run_if(not(playing_online))
+                        .before(handle_tile_revealed),
+                    update_autoplay_text,
+                    save_autoplay_settings.run_if(resource_changed::<AutoplaySettings>),
+                )
+                    .chain(),
+            );
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{
+    solver::Planner,
+    storage::{load_json, save_json},
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub const AUTOPLAY_SPEEDS: [f32; 5] = [1.0, 2.0, 4.0, 8.0, 16.0];
+
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoplaySettings {
+    pub moves_per_second: f32,
+    /// Guesses the cell least likely to hold a mine when stuck instead of waiting for the player.
+    pub guess: bool,
+}
+
+impl Default for AutoplaySettings {
+    fn default() -> Self {
+        Self {
+            moves_per_second: 4.0,
+            guess: false,
+        }
+    }
+}
+
+impl AutoplaySettings {
+    const FILE_NAME: &'static str = "autoplay.json";
+
+    pub fn load() -> Self {
+        let settings: Self = load_json(Self::FILE_NAME);
+        Self {
+            moves_per_second: settings.moves_per_second.clamp(
+                AUTOPLAY_SPEEDS[0],
+                AUTOPLAY_SPEEDS[AUTOPLAY_SPEEDS.len() - 1],
+            ),
+            ..settings
+        }
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = save_json(Self::FILE_NAME, self) {
+            warn!("Failed to save autoplay settings: {err}");
+        }
+    }
+
+    pub fn next_speed(&self) -> f32 {
+        AUTOPLAY_SPEEDS
+            .into_iter()
+            .find(|speed| *speed > self.moves_per_second)
+            .unwrap_or(AUTOPLAY_SPEEDS[0])
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct Autoplay {
+    pub enabled: bool,
+    /// Deals and plays board after board until the player takes over.
+    pub demo: bool,
+    /// Waiting for the player, as the next move would be a guess.
+    pub stuck: bool,
+    /// Seconds until the next move, or until the demo deals again.
+    pub cooldown: f32,
+    /// Seconds without any input, for starting the demo on its own.
+    pub idle: f32,
+    /// The moves the last deductions proved, still to play.
+    pub planner: Planner,
+}
+
+impl Autoplay {
+    pub fn start(&mut self, demo: bool) {
+        *self = Self {
+            enabled: true,
+            demo,
+            ..default()
+        };
+    }
+
+    pub fn stop(&mut self) {
+        *self = Self::default();
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{
+    autoplay::*,
+    board::{BoardSettings, grid::tile_position},
+    game::{GameState, GameStats, events::*, systems::minefield},
+    input::systems::click_tile,
+    mode::{GameMode, ModeRun},
+    rules::Minefield,
+    solver::Planner,
+    tile::{Position, Tile, TileSprite},
+};
+use bevy::prelude::*;
+
+const AUTOPLAY_FONT_SIZE: f32 = 14.0;
+/// Seconds without input on an untouched board before the demo starts by itself.
+const ATTRACT_DELAY: f32 = 60.0;
+/// Seconds the demo leaves a finished board up before dealing the next.
+const DEMO_RESTART_DELAY: f32 = 2.0;
+
+pub fn spawn_autoplay_text(mut commands: Commands) {
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font_size: AUTOPLAY_FONT_SIZE,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(6.0),
+            right: Val::Px(8.0),
+            padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0., 0., 0., 0.6)),
+        Visibility::Hidden,
+        AutoplayText,
+    ));
+}
+
+fn any_input(
+    keyboard_input: &ButtonInput<KeyCode>,
+    mouse_input: &ButtonInput<MouseButton>,
+) -> bool {
+    keyboard_input.get_just_pressed().next().is_some()
+        || mouse_input.get_just_pressed().next().is_some()
+}
+
+/// G lets the solver play the current board and D starts the demo; the player pressing
+/// anything else ends the demo.
+pub fn toggle_autoplay(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    state: Res<State<GameState>>,
+    mut autoplay: ResMut<Autoplay>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyG) {
+        if autoplay.enabled {
+            autoplay.stop();
+        } else if *state.get() == GameState::Playing {
+            autoplay.start(false);
+        }
+    } else if keyboard_input.just_pressed(KeyCode::KeyD) {
+        if autoplay.demo {
+            autoplay.stop();
+        } else if *state.get() != GameState::Editing {
+            autoplay.start(true);
+        }
+    } else if autoplay.demo && any_input(&keyboard_input, &mouse_input) {
+        autoplay.stop();
+    }
+}
+
+/// Starts the demo when a fresh board has been left alone for a while, except on the daily
+/// board, where solver moves would use up the day's attempt.
+pub fn start_attract_mode(
+    time: Res<Time>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    state: Res<State<GameState>>,
+    run: Res<ModeRun>,
+    game_stats: Res<GameStats>,
+    mut autoplay: ResMut<Autoplay>,
+) {
+    if any_input(&keyboard_input, &mouse_input) {
+        autoplay.idle = 0.0;
+        return;
+    }
+
+    autoplay.idle += time.delta_secs();
+    if !autoplay.enabled
+        && autoplay.idle >= ATTRACT_DELAY
+        && *state.get() == GameState::Playing
+        && run.mode != GameMode::Daily
+        && game_stats.clicks() == 0
+    {
+        autoplay.start(true);
+    }
+}
+
+/// Autoplay ends with its board, except in the demo, which deals another after a pause.
+pub fn deal_next_demo_board(
+    time: Res<Time>,
+    state: Res<State<GameState>>,
+    mut autoplay: ResMut<Autoplay>,
+    mut shown: Local<f32>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+) {
+    if !autoplay.enabled || *state.get() == GameState::Playing {
+        *shown = 0.0;
+        return;
+    }
+    if !autoplay.demo || *state.get() == GameState::Editing {
+        autoplay.stop();
+        return;
+    }
+
+    *shown += time.delta_secs();
+    if *shown >= DEMO_RESTART_DELAY {
+        *shown = 0.0;
+        new_game_events.write(NewGameEvent);
+    }
+}
+
+/// An opened number next to `cell` whose flags are all placed and whose other hidden
+/// neighbours are all safe, so chording it opens `cell` along with the rest.
+fn chord_target(cell: usize, field: &Minefield, planner: &Planner) -> Option<usize> {
+    field.neighbours[cell].iter().copied().find(|number| {
+        field.can_chord(*number)
+            && field
+                .chord_targets(*number)
+                .into_iter()
+                .all(|other| other == cell || planner.is_safe(other))
+    })
+}
+
+/// Plays one move whenever the speed allows: everything the last deductions proved, by
+/// chording where that opens the cell, then a guess if allowed.
+#[allow(clippy::too_many_arguments)]
+pub fn run_autoplay(
+    time: Res<Time>,
+    settings: Res<BoardSettings>,
+    autoplay_settings: Res<AutoplaySettings>,
+    mut autoplay: ResMut<Autoplay>,
+    changed_tiles: Query<(), Changed<Tile>>,
+    new_tiles: Query<(), Added<TileSprite>>,
+    tile_query: Query<(&Position, &Tile), With<TileSprite>>,
+    mut game_stats: ResMut<GameStats>,
+    mut tile_flagged_events: EventWriter<TileFlaggedEvent>,
+    mut tile_revealed_events: EventWriter<TileRevealedEvent>,
+    mut chord_events: EventWriter<ChordEvent>,
+) {
+    if !autoplay.enabled {
+        return;
+    }
+    if !changed_tiles.is_empty() {
+        autoplay.stuck = false;
+    }
+    if !new_tiles.is_empty() {
+        autoplay.planner = Planner::default();
+    }
+
+    autoplay.cooldown -= time.delta_secs();
+    if autoplay.stuck || autoplay.cooldown > 0.0 {
+        return;
+    }
+    autoplay.cooldown = autoplay.cooldown.max(0.0) + 1.0 / autoplay_settings.moves_per_second;
+
+    let field = minefield(&settings, tile_query.iter());
+    let board = field.visible();
+    let (cell, left_click) = match autoplay.planner.next_move(&board) {
+        Some((cell, 0)) => {
+            let target = chord_target(cell, &field, &autoplay.planner);
+            (target.unwrap_or(cell), true)
+        }
+        Some((cell, _)) => (cell, false),
+        None if autoplay_settings.guess || autoplay.demo => match board.safest_guess() {
+            Some(cell) => (cell, true),
+            None => return,
+        },
+        None => {
+            autoplay.stuck = true;
+            return;
+        }
+    };
+
+    let Some(tile) = field.tile(cell).cloned() else {
+        return;
+    };
+    game_stats.assisted = true;
+    click_tile(
+        tile_position(cell, &settings),
+        &tile,
+        left_click,
+        &tile_query,
+        &settings,
+        &mut game_stats,
+        &mut tile_flagged_events,
+        &mut tile_revealed_events,
+        &mut chord_events,
+    );
+}
+
+pub fn update_autoplay_text(
+    autoplay: Res<Autoplay>,
+    autoplay_settings: Res<AutoplaySettings>,
+    mut text: Single<(&mut Text, &mut Visibility), With<AutoplayText>>,
+) {
+    let (text, visibility) = &mut *text;
+    if !autoplay.enabled {
+        **visibility = Visibility::Hidden;
+        return;
+    }
+
+    let content = if autoplay.demo {
+        "Demo: the solver is playing. Press any key to take over".to_string()
+    } else if autoplay.stuck {
+        "Autoplay: stuck, the next move is a guess. G stop".to_string()
+    } else {
+        format!(
+            "Autoplay: {} moves/s. G stop",
+            autoplay_settings.moves_per_second
+        )
+    };
+    if text.0 != content {
+        text.0 = content;
+    }
+    **visibility = Visibility::Visible;
+}
+
+pub fn save_autoplay_settings(autoplay_settings: Res<AutoplaySettings>) {
+    if !autoplay_settings.is_added() {
+        autoplay_settings.save();
+    }
+}
@@ -0,0 +1,217 @@
+//! Runs bots against the game's rules: `serve` lets one program play over stdin/stdout, and
+//! `match` plays bots against each other on the same boards.
+
+use minesweeper::{
+    board::{BoardSettings, Preset},
+    bot::{self, Action, Bot, ProcessBot, Reply, Session, SolverBot},
+    engine::Outcome,
+    options::{BoardOptions, number_arg},
+    solver::guess_seed,
+};
+use serde::Deserialize;
+use std::{
+    env,
+    io::{self, BufRead, Write},
+    process::ExitCode,
+};
+
+const USAGE: &str = "\
+Usage:
+  minesweeper-bot serve [board options]
+  minesweeper-bot match [board options] [--games N] [--max-moves N] BOT...
+
+Board options:
+  --preset default|beginner|intermediate|expert
+  --width N  --height N  --mines N
+  --seed N
+  --multi-mine
+
+serve prints the board as JSON, then answers each line read from stdin:
+  {\"action\": \"reveal\" | \"flag\" | \"chord\", \"x\": X, \"y\": Y}  plays a move
+  {\"action\": \"view\"}                                     prints the board again
+  {\"action\": \"new\", \"seed\": N}                            deals a new board
+match runs each BOT on the same seeded boards. A BOT is `solver` or a shell command that
+is sent the board and then a reply per move, as with serve, and prints a move per line.";
+
+const DEFAULT_GAMES: u64 = 100;
+const DEFAULT_MAX_MOVES: u32 = 10_000;
+
+#[derive(Default)]
+struct Options {
+    board: BoardOptions,
+    games: Option<u64>,
+    max_moves: Option<u32>,
+    bots: Vec<String>,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            if options.board.parse_arg(arg, &mut args)? {
+                continue;
+            }
+            match arg.as_str() {
+                "--games" => options.games = Some(number_arg(arg, &mut args)?),
+                "--max-moves" => options.max_moves = Some(number_arg(arg, &mut args)?),
+                _ if !arg.starts_with("--") => options.bots.push(arg.clone()),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn settings(&self) -> Result<BoardSettings, String> {
+        self.board
+            .settings(self.board.preset.unwrap_or(Preset::Default))
+    }
+}
+
+/// Lines `serve` accepts besides moves.
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+enum Control {
+    View,
+    New {
+        #[serde(default)]
+        seed: Option<u64>,
+    },
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let Some((command, rest)) = args.split_first() else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    let result = Options::parse(rest).and_then(|options| match command.as_str() {
+        "serve" => serve(&options),
+        "match" => run_match(&options),
+        "help" | "--help" | "-h" => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        _ => Err(format!("unknown command '{command}'")),
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn serve(options: &Options) -> Result<(), String> {
+    let mut settings = options.settings()?;
+    let mut session = Session::new(&settings);
+    let mut stdout = io::stdout().lock();
+    let io_error = |err: io::Error| err.to_string();
+
+    print_json(&mut stdout, &session.view()).map_err(io_error)?;
+    for line in io::stdin().lock().lines() {
+        let line = line.map_err(io_error)?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Ok(action) = serde_json::from_str::<Action>(&line) {
+            print_json(&mut stdout, &session.reply(action)).map_err(io_error)?;
+            continue;
+        }
+        match serde_json::from_str::<Control>(&line) {
+            Ok(Control::View) => print_json(&mut stdout, &session.view()),
+            Ok(Control::New { seed }) => {
+                settings.seed = seed;
+                session = Session::new(&settings);
+                print_json(&mut stdout, &session.view())
+            }
+            Err(err) => {
+                let reply = Reply {
+                    ok: false,
+                    changed: false,
+                    error: Some(format!("could not read '{line}': {err}")),
+                    view: session.view(),
+                };
+                print_json(&mut stdout, &reply)
+            }
+        }
+        .map_err(io_error)?;
+    }
+
+    Ok(())
+}
+
+fn print_json<T: serde::Serialize>(out: &mut impl Write, value: &T) -> io::Result<()> {
+    serde_json::to_writer(&mut *out, value)?;
+    writeln!(out)?;
+    out.flush()
+}
+
+#[derive(Default)]
+struct Score {
+    wins: u32,
+    moves: u64,
+    errors: u32,
+}
+
+fn run_match(options: &Options) -> Result<(), String> {
+    if options.bots.is_empty() {
+        return Err("match needs at least one bot".to_string());
+    }
+    let games = options.games.unwrap_or(DEFAULT_GAMES).max(1);
+    let max_moves = options.max_moves.unwrap_or(DEFAULT_MAX_MOVES);
+    let first_seed = options.board.seed.unwrap_or(1);
+    let mut scores: Vec<Score> = options.bots.iter().map(|_| Score::default()).collect();
+
+    for game in 0..games {
+        let settings = BoardSettings {
+            seed: Some(first_seed + game),
+            ..options.settings()?
+        };
+
+        for (name, score) in options.bots.iter().zip(&mut scores) {
+            let mut bot: Box<dyn Bot> = match name.as_str() {
+                "solver" => Box::new(SolverBot::new(&settings, guess_seed(first_seed + game))),
+                command => Box::new(ProcessBot::spawn(command).map_err(|err| err.to_string())?),
+            };
+            let mut session = Session::new(&settings);
+            if let Err(err) = bot::play(bot.as_mut(), &mut session, max_moves) {
+                eprintln!("{} failed on game {}: {err}", bot.name(), game + 1);
+                score.errors += 1;
+            }
+            score.wins += (session.outcome() == Outcome::Won) as u32;
+            score.moves += session.moves as u64;
+        }
+    }
+
+    let width = options
+        .bots
+        .iter()
+        .map(String::len)
+        .max()
+        .unwrap_or(0)
+        .max(3);
+    println!(
+        "{:<width$}{:>7}{:>7}{:>8}{:>11}{:>8}",
+        "bot", "games", "wins", "win %", "avg moves", "errors"
+    );
+    for (name, score) in options.bots.iter().zip(&scores) {
+        println!(
+            "{:<width$}{:>7}{:>7}{:>7.1}%{:>11.1}{:>8}",
+            name,
+            games,
+            score.wins,
+            score.wins as f32 * 100.0 / games as f32,
+            score.moves as f32 / games as f32,
+            score.errors
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,286 @@
+//! Generates, solves and benchmarks boards from the command line, without opening a window.
+
+use minesweeper::{
+    board::{BoardSettings, Preset, formats::BoardFormat},
+    engine::{Engine, Outcome},
+    options::{BoardOptions, number_arg},
+    solver::guess_seed,
+};
+use rand::{prelude::*, rngs::StdRng};
+use std::{env, fs, path::Path, process::ExitCode, time::Instant};
+
+const USAGE: &str = "\
+Usage:
+  minesweeper-cli generate [board options] [--raw]
+  minesweeper-cli solve <board file>
+  minesweeper-cli bench [--preset NAME] [--games N] [--seed N]
+
+Board options:
+  --preset default|beginner|intermediate|expert
+  --width N  --height N  --mines N
+  --seed N
+  --multi-mine
+
+--raw prints the board in the text format `solve` and the game import.
+Without --preset, bench runs every preset.";
+
+const DEFAULT_GAMES: usize = 1000;
+const DEFAULT_BENCH_SEED: u64 = 1;
+
+#[derive(Default)]
+struct Options {
+    board: BoardOptions,
+    raw: bool,
+    games: Option<usize>,
+    file: Option<String>,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            if options.board.parse_arg(arg, &mut args)? {
+                continue;
+            }
+            match arg.as_str() {
+                "--games" => options.games = Some(number_arg(arg, &mut args)?),
+                "--raw" => options.raw = true,
+                _ if !arg.starts_with("--") && options.file.is_none() => {
+                    options.file = Some(arg.clone());
+                }
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let Some((command, rest)) = args.split_first() else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    let result = Options::parse(rest).and_then(|options| match command.as_str() {
+        "generate" => generate(&options),
+        "solve" => solve(&options),
+        "bench" => bench(&options),
+        "help" | "--help" | "-h" => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        _ => Err(format!("unknown command '{command}'")),
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn generate(options: &Options) -> Result<(), String> {
+    let preset = options.board.preset.unwrap_or(Preset::Default);
+    let mut settings = options.board.settings(preset)?;
+    let seed = *settings.seed.get_or_insert_with(rand::random);
+    let engine = Engine::new(&settings);
+
+    if options.raw {
+        println!("{}", engine.layout().to_rows().join("\n"));
+        return Ok(());
+    }
+
+    println!(
+        "{} {}x{}, {} mines, seed {seed}",
+        preset.name(),
+        settings.width,
+        settings.height,
+        settings.mine_count
+    );
+    report(&engine);
+    Ok(())
+}
+
+fn solve(options: &Options) -> Result<(), String> {
+    let path = options.file.as_deref().ok_or("solve needs a board file")?;
+    let path = Path::new(path);
+    let format = BoardFormat::from_path(path).map_err(|err| err.to_string())?;
+    let bytes =
+        fs::read(path).map_err(|err| format!("could not read {}: {err}", path.display()))?;
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let layout = format
+        .import(&name, &bytes)
+        .map_err(|err| err.to_string())?;
+
+    let mut settings = BoardSettings::default();
+    settings.apply_layout(layout);
+    let engine = Engine::new(&settings);
+
+    println!(
+        "{} {}x{}, {} mines",
+        path.display(),
+        settings.width,
+        settings.height,
+        settings.mine_count
+    );
+    report(&engine);
+    Ok(())
+}
+
+fn report(engine: &Engine) {
+    for row in ascii_rows(engine) {
+        println!("{row}");
+    }
+    println!("3BV: {}", engine.three_bv());
+
+    let (start, solved) = solve_without_guessing(engine);
+    let from = match start {
+        Some(cell) => format!("starting at {}", cell_name(engine, cell)),
+        None => "from the cells it starts open with".to_string(),
+    };
+    let safe_cells = safe_cells(engine);
+    let opened = (0..engine.cell_count())
+        .filter(|cell| solved.is_revealed(*cell) && !solved.is_mine(*cell))
+        .count();
+    if solved.outcome == Outcome::Won {
+        println!("No-guess: yes, {from}");
+    } else {
+        println!("No-guess: no, {from}; logic opens {opened} of {safe_cells} safe cells");
+    }
+}
+
+/// The board top row first: `*` for mines, numbers, `.` for blanks and spaces for holes.
+fn ascii_rows(engine: &Engine) -> Vec<String> {
+    let width = engine.settings.width as usize;
+    (0..engine.settings.height as usize)
+        .rev()
+        .map(|y| {
+            (0..width)
+                .map(|x| {
+                    let cell = y * width + x;
+                    match engine.number(cell) {
+                        _ if !engine.has_cell(cell) => ' ',
+                        _ if engine.is_mine(cell) => '*',
+                        0 => '.',
+                        number => char::from(b'0' + number),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn cell_name(engine: &Engine, cell: usize) -> String {
+    let width = engine.settings.width as usize;
+    let row = engine.settings.height as usize - cell / width;
+    format!("column {}, row {row}", cell % width + 1)
+}
+
+fn safe_cells(engine: &Engine) -> usize {
+    (0..engine.cell_count())
+        .filter(|cell| engine.has_cell(*cell) && !engine.is_mine(*cell))
+        .count()
+}
+
+/// Plays on pure logic, from the opening cell unless the board starts with cells open.
+fn solve_without_guessing(engine: &Engine) -> (Option<usize>, Engine) {
+    let mut solved = engine.clone();
+    let start = if (0..engine.cell_count()).any(|cell| engine.is_revealed(cell)) {
+        None
+    } else {
+        engine.opening_cell()
+    };
+
+    if let Some(cell) = start {
+        solved.reveal(cell);
+    }
+    while solved.outcome == Outcome::Playing && solved.apply_deductions() {}
+    (start, solved)
+}
+
+/// Plays the board as dealt: a first click in the middle, then logic, guessing at random
+/// among the cells logic cannot place whenever it gets stuck.
+fn play_with_guesses(engine: &Engine, rng: &mut StdRng) -> Outcome {
+    let mut game = engine.clone();
+    let width = game.settings.width as usize;
+    let middle = game.settings.height as usize / 2 * width + width / 2;
+    if game.is_hidden(middle) {
+        game.reveal(middle);
+    }
+
+    while game.outcome == Outcome::Playing {
+        if game.apply_deductions() {
+            continue;
+        }
+
+        let unknown: Vec<usize> = (0..game.cell_count())
+            .filter(|cell| game.is_hidden(*cell) && game.flags(*cell) == 0)
+            .collect();
+        let Some(cell) = unknown.choose(rng) else {
+            break;
+        };
+        game.reveal(*cell);
+    }
+
+    game.outcome
+}
+
+fn bench(options: &Options) -> Result<(), String> {
+    let presets = match options.board.preset {
+        Some(preset) => vec![preset],
+        None => Preset::ALL.to_vec(),
+    };
+    let games = options.games.unwrap_or(DEFAULT_GAMES).max(1);
+    let first_seed = options.board.seed.unwrap_or(DEFAULT_BENCH_SEED);
+
+    println!(
+        "{:<14}{:>7}{:>10}{:>13}{:>9}",
+        "preset", "games", "no-guess", "solver wins", "avg 3BV"
+    );
+    for preset in presets {
+        let started = Instant::now();
+        let mut no_guess = 0;
+        let mut wins = 0;
+        let mut total_three_bv = 0;
+
+        for game in 0..games as u64 {
+            let seed = first_seed + game;
+            let settings = BoardSettings {
+                seed: Some(seed),
+                ..options.board.settings(preset)?
+            };
+            let engine = Engine::new(&settings);
+            total_three_bv += engine.three_bv() as usize;
+
+            if solve_without_guessing(&engine).1.outcome == Outcome::Won {
+                no_guess += 1;
+            }
+            let mut rng = StdRng::seed_from_u64(guess_seed(seed));
+            if play_with_guesses(&engine, &mut rng) == Outcome::Won {
+                wins += 1;
+            }
+        }
+
+        let percent = |count: usize| count as f32 * 100.0 / games as f32;
+        println!(
+            "{:<14}{:>7}{:>9.1}%{:>12.1}%{:>9.1}",
+            preset.name(),
+            games,
+            percent(no_guess),
+            percent(wins),
+            total_three_bv as f32 / games as f32
+        );
+        eprintln!("{} took {:.2?}", preset.name(), started.elapsed());
+    }
+
+    Ok(())
+}
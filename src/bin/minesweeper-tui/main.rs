@@ -0,0 +1,59 @@
+//! Plays in a terminal, for machines without a GPU or over SSH.
+
+#[cfg(unix)]
+mod play;
+#[cfg(unix)]
+mod terminal;
+
+use minesweeper::{
+    board::{BoardSettings, Preset},
+    options::BoardOptions,
+};
+use std::{env, process::ExitCode};
+
+const USAGE: &str = "\
+Usage: minesweeper-tui [--preset default|beginner|intermediate|expert]
+                       [--width N] [--height N] [--mines N] [--seed N] [--multi-mine]";
+
+/// The board to start on, and its preset unless the size was given by hand.
+fn parse_args(args: &[String]) -> Result<(Option<Preset>, BoardSettings), String> {
+    let mut options = BoardOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !options.parse_arg(arg, &mut args)? {
+            return Err(format!("unexpected argument '{arg}'"));
+        }
+    }
+
+    let preset = options.preset.unwrap_or(Preset::Default);
+    let settings = options.settings(preset)?;
+    Ok(((!options.is_custom()).then_some(preset), settings))
+}
+
+#[cfg(unix)]
+fn run(preset: Option<Preset>, settings: BoardSettings) -> ExitCode {
+    match play::run(preset, settings) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn run(_: Option<Preset>, _: BoardSettings) -> ExitCode {
+    eprintln!("minesweeper-tui needs a Unix terminal");
+    ExitCode::FAILURE
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match parse_args(&args) {
+        Ok((preset, settings)) => run(preset, settings),
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use crate::terminal::{Input, RESET, RawTerminal, background, foreground};
+use minesweeper::{
+    board::{BoardSettings, Preset, grid::tile_index},
+    engine::{Engine, Outcome},
+    game::GameStats,
+    mode::{GameMode, ModeRecords},
+    tile::{
+        Position,
+        colors::{Rgb, fill_color, label},
+    },
+};
+use std::{io, time::Instant};
+
+/// Terminal row of the board's top row, counted from 1.
+const BOARD_TOP: u16 = 3;
+const CELL_WIDTH: u16 = 3;
+
+const HELP: &str = "Arrows/hjkl move  Space open  F flag  R new game  P preset  Q quit  Mouse: left open, right flag";
+
+/// A classic game with the window's board rules, clock and records.
+pub struct Game {
+    preset: Option<Preset>,
+    settings: BoardSettings,
+    engine: Engine,
+    stats: GameStats,
+    cursor: Position,
+    records: ModeRecords,
+    recorded: bool,
+}
+
+impl Game {
+    pub fn new(preset: Option<Preset>, settings: BoardSettings) -> Self {
+        let (engine, stats) = deal(&settings);
+        Self {
+            preset,
+            cursor: board_centre(&settings),
+            settings,
+            engine,
+            stats,
+            records: ModeRecords::load(),
+            recorded: false,
+        }
+    }
+
+    fn new_board(&mut self) {
+        (self.engine, self.stats) = deal(&self.settings);
+        self.cursor = board_centre(&self.settings);
+        self.recorded = false;
+    }
+
+    fn next_preset(&mut self) {
+        let next = match self.preset {
+            Some(preset) => {
+                let index = Preset::ALL.iter().position(|other| *other == preset);
+                Preset::ALL[index.map_or(0, |index| (index + 1) % Preset::ALL.len())]
+            }
+            None => Preset::Default,
+        };
+        self.preset = Some(next);
+        self.settings = BoardSettings {
+            multi_mine: self.settings.multi_mine,
+            ..next.settings()
+        };
+        self.new_board();
+    }
+
+    fn click(&mut self, position: Position, left_click: bool) {
+        if self.engine.outcome != Outcome::Playing {
+            return;
+        }
+
+        let cell = tile_index(position, &self.settings);
+        let (kind, wasted) = self.engine.click(cell, left_click);
+        self.stats.record_click(position, kind, wasted);
+
+        self.stats.mines_remaining = self.engine.board.mines_left();
+        self.record_result();
+    }
+
+    fn record_result(&mut self) {
+        let won = match self.engine.outcome {
+            Outcome::Playing => return,
+            Outcome::Won => true,
+            Outcome::Lost => false,
+        };
+        // Custom boards are not comparable with the Classic presets, so only presets are kept.
+        if self.recorded || self.preset.is_none() {
+            return;
+        }
+
+        self.recorded = true;
+        self.records.record(GameMode::Classic, won as u32);
+        if won {
+            self.records.record_win(GameMode::Classic, &self.stats);
+        }
+        // A failed save keeps the records for this session; the terminal has nowhere to warn.
+        let _ = self.records.save();
+    }
+
+    fn tick(&mut self, seconds: f32) {
+        if self.engine.outcome == Outcome::Playing && self.stats.clicks() > 0 {
+            self.stats.seconds += seconds;
+        }
+    }
+
+    fn move_cursor(&mut self, dx: i16, dy: i16) {
+        let x = (self.cursor.x as i16 + dx).clamp(0, self.settings.width as i16 - 1);
+        let y = (self.cursor.y as i16 + dy).clamp(0, self.settings.height as i16 - 1);
+        self.cursor = Position {
+            x: x as u8,
+            y: y as u8,
+        };
+    }
+
+    /// The cell under a terminal column and row, if any.
+    fn cell_at(&self, column: u16, row: u16) -> Option<Position> {
+        let x = column.checked_sub(1)? / CELL_WIDTH;
+        let from_top = row.checked_sub(BOARD_TOP)?;
+        if x >= self.settings.width as u16 || from_top >= self.settings.height as u16 {
+            return None;
+        }
+
+        let position = Position {
+            x: x as u8,
+            y: self.settings.height - 1 - from_top as u8,
+        };
+        self.settings.has_cell(position).then_some(position)
+    }
+
+    /// Applies one input; returns false to quit.
+    fn handle(&mut self, input: Input) -> bool {
+        match input {
+            Input::Quit | Input::Char('q') | Input::Char('Q') => return false,
+            Input::Up | Input::Char('k') => self.move_cursor(0, 1),
+            Input::Down | Input::Char('j') => self.move_cursor(0, -1),
+            Input::Left | Input::Char('h') => self.move_cursor(-1, 0),
+            Input::Right | Input::Char('l') => self.move_cursor(1, 0),
+            Input::Char(' ') | Input::Char('\n') => self.click(self.cursor, true),
+            Input::Char('f') | Input::Char('F') => self.click(self.cursor, false),
+            Input::Char('r') | Input::Char('R') => self.new_board(),
+            Input::Char('p') | Input::Char('P') => self.next_preset(),
+            Input::Click { column, row, left } => {
+                if let Some(position) = self.cell_at(column, row) {
+                    self.cursor = position;
+                    self.click(position, left);
+                }
+            }
+            Input::Char(_) => {}
+        }
+        true
+    }
+
+    fn render(&self) -> String {
+        let name = self.preset.map_or("custom", |preset| preset.name());
+        let mut lines = vec![
+            format!(
+                "Minesweeper  {name} {}x{}   Mines {}   Time {}   3BV {}   Clicks {}",
+                self.settings.width,
+                self.settings.height,
+                self.stats.mines_remaining,
+                self.stats.seconds as u32,
+                self.stats.three_bv,
+                self.stats.clicks()
+            ),
+            String::new(),
+        ];
+
+        for y in (0..self.settings.height).rev() {
+            let row: String = (0..self.settings.width)
+                .map(|x| self.render_cell(Position { x, y }))
+                .collect();
+            lines.push(row + RESET);
+        }
+
+        lines.push(String::new());
+        lines.push(match self.engine.outcome {
+            Outcome::Playing => String::new(),
+            Outcome::Won => format!(
+                "You won! Time {:.2}s  3BV/s {:.2}  IOE {:.2}  RQP {:.1}  Best 3BV/s {:.2}",
+                self.stats.seconds,
+                self.stats.three_bv_per_second(),
+                self.stats.ioe(),
+                self.stats.rqp(),
+                self.records.get(GameMode::Classic).best_three_bv_per_second
+            ),
+            Outcome::Lost => "Game over! Press R to restart".to_string(),
+        });
+        lines.push(HELP.to_string());
+
+        let mut screen = "\x1b[H".to_string();
+        for line in lines {
+            screen.push_str(&line);
+            screen.push_str("\x1b[K\r\n");
+        }
+        screen.push_str("\x1b[J");
+        screen
+    }
+
+    fn render_cell(&self, position: Position) -> String {
+        let Some(tile) = self.engine.board.tile(tile_index(position, &self.settings)) else {
+            return format!("{RESET}{}", " ".repeat(CELL_WIDTH as usize));
+        };
+        let fill = fill_color(tile);
+        let plain = contrast(fill);
+        let (text, color) = label(tile, self.settings.multi_mine)
+            .or_else(|| {
+                if tile.is_wrong_flag() {
+                    Some(("X".to_string(), plain))
+                } else if tile.is_revealed && tile.is_mine() {
+                    Some(("*".to_string(), plain))
+                } else if tile.is_flagged() {
+                    Some(("F".to_string(), plain))
+                } else {
+                    None
+                }
+            })
+            .unwrap_or((" ".to_string(), plain));
+
+        let (left, right) = if position == self.cursor {
+            ("[", "]")
+        } else {
+            (" ", " ")
+        };
+        format!(
+            "{}{}{left}{}{text}{}{right}",
+            background(fill),
+            foreground(plain),
+            foreground(color),
+            foreground(plain),
+        )
+    }
+}
+
+/// Black or white, whichever reads better on `fill`.
+fn contrast(fill: Rgb) -> Rgb {
+    if fill.luminance() > 0.4 {
+        Rgb::BLACK
+    } else {
+        Rgb::WHITE
+    }
+}
+
+/// A fresh board for `settings` and the stats to play it with.
+fn deal(settings: &BoardSettings) -> (Engine, GameStats) {
+    let engine = Engine::new(settings);
+    let stats = GameStats {
+        three_bv: engine.three_bv(),
+        ..GameStats::for_board(settings)
+    };
+    (engine, stats)
+}
+
+fn board_centre(settings: &BoardSettings) -> Position {
+    Position {
+        x: settings.width / 2,
+        y: settings.height / 2,
+    }
+}
+
+pub fn run(preset: Option<Preset>, settings: BoardSettings) -> io::Result<()> {
+    let terminal = RawTerminal::enter()?;
+    let mut game = Game::new(preset, settings);
+    let mut last_frame = Instant::now();
+
+    loop {
+        terminal.write(&game.render())?;
+        let inputs = terminal.read()?;
+
+        game.tick(last_frame.elapsed().as_secs_f32());
+        last_frame = Instant::now();
+        for input in inputs {
+            if !game.handle(input) {
+                return Ok(());
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use minesweeper::tile::colors::Rgb;
+use std::io::{self, Read, Write};
+
+// Alternate screen, hidden cursor and SGR mouse reports, and their undoing.
+const ENTER: &str = "\x1b[?1049h\x1b[?25l\x1b[?1000h\x1b[?1006h\x1b[2J";
+const LEAVE: &str = "\x1b[?1006l\x1b[?1000l\x1b[?25h\x1b[?1049l";
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Input {
+    Up,
+    Down,
+    Left,
+    Right,
+    Char(char),
+    /// A press at a 1-based terminal column and row.
+    Click {
+        column: u16,
+        row: u16,
+        left: bool,
+    },
+    Quit,
+}
+
+/// Puts the terminal in raw mode for as long as it lives; reads give up after a tenth of a
+/// second so the clock keeps ticking.
+pub struct RawTerminal {
+    original: libc::termios,
+}
+
+impl RawTerminal {
+    pub fn enter() -> io::Result<Self> {
+        // SAFETY: termios is plain data and both calls only touch the struct we pass.
+        let original = unsafe {
+            let mut original = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut original) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            original
+        };
+
+        let mut raw = original;
+        // SAFETY: as above.
+        unsafe {
+            libc::cfmakeraw(&mut raw);
+            raw.c_cc[libc::VMIN] = 0;
+            raw.c_cc[libc::VTIME] = 1;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, &raw) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+
+        let terminal = Self { original };
+        terminal.write(ENTER)?;
+        Ok(terminal)
+    }
+
+    pub fn write(&self, text: &str) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(text.as_bytes())?;
+        stdout.flush()
+    }
+
+    /// Whatever arrived since the last read, possibly nothing.
+    pub fn read(&self) -> io::Result<Vec<Input>> {
+        let mut buffer = [0; 256];
+        let count = io::stdin().lock().read(&mut buffer)?;
+        Ok(parse_input(&buffer[..count]))
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = self.write(LEAVE);
+        // SAFETY: restores the settings read in `enter`.
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, &self.original);
+        }
+    }
+}
+
+fn parse_input(mut bytes: &[u8]) -> Vec<Input> {
+    let mut inputs = Vec::new();
+
+    while let Some((&first, rest)) = bytes.split_first() {
+        bytes = rest;
+        match first {
+            // Ctrl-C, since raw mode turns off signals.
+            3 => inputs.push(Input::Quit),
+            b'\r' | b'\n' => inputs.push(Input::Char('\n')),
+            0x1b => {
+                if let [b'[', b'<', rest @ ..] = bytes {
+                    let end = rest
+                        .iter()
+                        .position(|byte| *byte == b'M' || *byte == b'm')
+                        .unwrap_or(rest.len());
+                    if let Some(input) = parse_mouse(&rest[..end], rest.get(end) == Some(&b'M')) {
+                        inputs.push(input);
+                    }
+                    bytes = rest.get(end + 1..).unwrap_or_default();
+                } else if let [b'[' | b'O', key, rest @ ..] = bytes {
+                    match key {
+                        b'A' => inputs.push(Input::Up),
+                        b'B' => inputs.push(Input::Down),
+                        b'C' => inputs.push(Input::Right),
+                        b'D' => inputs.push(Input::Left),
+                        _ => {}
+                    }
+                    bytes = rest;
+                } else {
+                    inputs.push(Input::Char('\x1b'));
+                }
+            }
+            byte if byte.is_ascii() => inputs.push(Input::Char(byte as char)),
+            _ => {}
+        }
+    }
+
+    inputs
+}
+
+/// An SGR report, `button;column;row`, ending in `M` for a press.
+fn parse_mouse(report: &[u8], pressed: bool) -> Option<Input> {
+    let report = std::str::from_utf8(report).ok()?;
+    let mut fields = report.split(';').map(|field| field.parse::<u16>().ok());
+    let (button, column, row) = (fields.next()??, fields.next()??, fields.next()??);
+
+    // Drags and wheel turns set higher bits; only plain left and right presses count.
+    match button {
+        0 if pressed => Some(Input::Click {
+            column,
+            row,
+            left: true,
+        }),
+        2 if pressed => Some(Input::Click {
+            column,
+            row,
+            left: false,
+        }),
+        _ => None,
+    }
+}
+
+pub fn foreground(Rgb(red, green, blue): Rgb) -> String {
+    format!("\x1b[38;2;{red};{green};{blue}m")
+}
+
+pub fn background(Rgb(red, green, blue): Rgb) -> String {
+    format!("\x1b[48;2;{red};{green};{blue}m")
+}
+
+pub const RESET: &str = "\x1b[0m";
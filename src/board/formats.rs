@@ -0,0 +1,225 @@
+use crate::board::layout::{BoardLayout, LayoutError};
+use serde::{Deserialize, Serialize};
+use std::{fmt, path::Path};
+
+/// Board file formats shared with other minesweeper tools.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BoardFormat {
+    /// One line per row, top first, `*` for mines and `.` for safe cells.
+    Text,
+    /// Minesweeper Board Format: width, height, a big-endian mine count, then an `x, y` byte
+    /// pair per mine counted from the top left.
+    Mbf,
+    /// The text rows wrapped in JSON with the board's metadata.
+    Json,
+}
+
+#[derive(Debug)]
+pub enum FormatError {
+    UnknownExtension,
+    Truncated,
+    MineOutOfBounds(u8, u8),
+    SizeMismatch,
+    /// The board uses something the format has no way to write down.
+    Unsupported(&'static str),
+    Json(String),
+    Layout(LayoutError),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FormatError::UnknownExtension => write!(f, "expected a .txt, .mbf or .json file"),
+            FormatError::Truncated => write!(f, "file ends early"),
+            FormatError::MineOutOfBounds(x, y) => write!(f, "mine at {x},{y} is off the board"),
+            FormatError::SizeMismatch => write!(f, "rows do not match the stated size"),
+            FormatError::Unsupported(feature) => write!(f, "format cannot store {feature}"),
+            FormatError::Json(error) => write!(f, "invalid JSON: {error}"),
+            FormatError::Layout(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+impl From<LayoutError> for FormatError {
+    fn from(error: LayoutError) -> Self {
+        FormatError::Layout(error)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardFile {
+    pub title: String,
+    #[serde(default)]
+    pub created: String,
+    pub width: u8,
+    pub height: u8,
+    pub mine_count: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    pub rows: Vec<String>,
+}
+
+/// Details stored alongside the board by formats that have room for them.
+#[derive(Debug, Clone, Default)]
+pub struct BoardInfo {
+    pub title: String,
+    pub created: String,
+    pub seed: Option<u64>,
+}
+
+impl BoardFormat {
+    pub const ALL: [BoardFormat; 3] = [BoardFormat::Text, BoardFormat::Mbf, BoardFormat::Json];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            BoardFormat::Text => "txt",
+            BoardFormat::Mbf => "mbf",
+            BoardFormat::Json => "json",
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Result<Self, FormatError> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+
+        Self::ALL
+            .into_iter()
+            .find(|format| extension.as_deref() == Some(format.extension()))
+            .ok_or(FormatError::UnknownExtension)
+    }
+
+    pub fn export(&self, layout: &BoardLayout, info: &BoardInfo) -> Result<Vec<u8>, FormatError> {
+        match self {
+            BoardFormat::Text => Ok((layout.to_rows().join("\n") + "\n").into_bytes()),
+            BoardFormat::Mbf => export_mbf(layout),
+            BoardFormat::Json => {
+                let file = BoardFile {
+                    title: info.title.clone(),
+                    created: info.created.clone(),
+                    width: layout.width,
+                    height: layout.height,
+                    mine_count: layout.mine_count(),
+                    seed: info.seed,
+                    rows: layout.to_rows(),
+                };
+                serde_json::to_vec_pretty(&file).map_err(|err| FormatError::Json(err.to_string()))
+            }
+        }
+    }
+
+    pub fn import(&self, name: &str, bytes: &[u8]) -> Result<BoardLayout, FormatError> {
+        match self {
+            BoardFormat::Text => {
+                let rows: Vec<String> = String::from_utf8_lossy(bytes)
+                    .lines()
+                    .map(|line| line.trim_end().to_string())
+                    .filter(|line| !line.is_empty())
+                    .collect();
+                Ok(BoardLayout::from_rows(name, &rows)?)
+            }
+            BoardFormat::Mbf => import_mbf(bytes),
+            BoardFormat::Json => {
+                let file: BoardFile = serde_json::from_slice(bytes)
+                    .map_err(|err| FormatError::Json(err.to_string()))?;
+                let layout = BoardLayout::from_rows(&file.title, &file.rows)?;
+                if (layout.width, layout.height) != (file.width, file.height) {
+                    return Err(FormatError::SizeMismatch);
+                }
+                Ok(layout)
+            }
+        }
+    }
+}
+
+fn export_mbf(layout: &BoardLayout) -> Result<Vec<u8>, FormatError> {
+    if layout.mask.is_some() {
+        return Err(FormatError::Unsupported("board shapes"));
+    }
+    if layout.mines.iter().any(|mines| *mines > 1) {
+        return Err(FormatError::Unsupported("multi-mine cells"));
+    }
+
+    let mine_count = layout.mine_count() as u16;
+    let mut bytes = vec![layout.width, layout.height];
+    bytes.extend(mine_count.to_be_bytes());
+
+    // Layouts count rows from the bottom, MBF from the top.
+    for y in (0..layout.height).rev() {
+        for x in 0..layout.width {
+            if layout.mines[y as usize * layout.width as usize + x as usize] > 0 {
+                bytes.extend([x, layout.height - 1 - y]);
+            }
+        }
+    }
+
+    Ok(bytes)
+}
+
+fn import_mbf(bytes: &[u8]) -> Result<BoardLayout, FormatError> {
+    let [width, height, high, low, mines @ ..] = bytes else {
+        return Err(FormatError::Truncated);
+    };
+    let mine_count = u16::from_be_bytes([*high, *low]) as usize;
+    if mines.len() < mine_count * 2 {
+        return Err(FormatError::Truncated);
+    }
+
+    let mut rows = vec![vec!['.'; *width as usize]; *height as usize];
+    for mine in mines.chunks_exact(2).take(mine_count) {
+        let (x, y) = (mine[0], mine[1]);
+        let cell = rows
+            .get_mut(y as usize)
+            .and_then(|row| row.get_mut(x as usize))
+            .ok_or(FormatError::MineOutOfBounds(x, y))?;
+        *cell = '*';
+    }
+
+    let rows: Vec<String> = rows.into_iter().map(String::from_iter).collect();
+    Ok(BoardLayout::from_rows("", &rows)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(rows: &[&str]) -> BoardLayout {
+        let rows: Vec<String> = rows.iter().map(|row| row.to_string()).collect();
+        BoardLayout::from_rows("", &rows).unwrap()
+    }
+
+    #[test]
+    fn mbf_round_trips_a_board() {
+        let board = layout(&["*..*", "....", ".*.."]);
+        let bytes = export_mbf(&board).unwrap();
+        assert_eq!(import_mbf(&bytes).unwrap(), board);
+    }
+
+    #[test]
+    fn mbf_counts_rows_from_the_top() {
+        let bytes = export_mbf(&layout(&["*..", "..."])).unwrap();
+        assert_eq!(bytes, vec![3, 2, 0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn mbf_rejects_truncated_files() {
+        let bytes = export_mbf(&layout(&["*..*", "...."])).unwrap();
+        for length in [0, 3, bytes.len() - 1] {
+            assert!(matches!(
+                import_mbf(&bytes[..length]),
+                Err(FormatError::Truncated)
+            ));
+        }
+    }
+
+    #[test]
+    fn mbf_rejects_mines_off_the_board() {
+        assert!(matches!(
+            import_mbf(&[2, 2, 0, 1, 2, 0]),
+            Err(FormatError::MineOutOfBounds(2, 0))
+        ));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{
+    board::{
+        BoardSettings,
+        grid::{neighbours, tile_index, tile_position},
+        layout::BoardLayout,
+    },
+    tile::Position,
+};
+use rand::{prelude::*, rng, rngs::StdRng};
+
+/// The board `settings` asks for, with the blank cells it starts open with already expanded.
+pub fn deal_board(settings: &BoardSettings) -> BoardLayout {
+    let mut layout = match &settings.layout {
+        Some(layout) => layout.clone(),
+        None => BoardLayout {
+            mines: generate_mines(settings),
+            mask: settings.mask.clone(),
+            ..BoardLayout::empty(settings.width, settings.height)
+        },
+    };
+    expand_openings(&mut layout.revealed, &layout.mines, settings);
+    layout
+}
+
+fn generate_mines(settings: &BoardSettings) -> Vec<u8> {
+    let mut rng = match settings.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_rng(&mut rng()),
+    };
+    let total_tiles = settings.width as usize * settings.height as usize;
+    let mut mines = vec![0; total_tiles];
+
+    let mut mines_placed = 0;
+    while mines_placed < settings.mine_count {
+        let index = rng.random_range(0..total_tiles);
+        let position = tile_position(index, settings);
+        if mines[index] == 0 && settings.has_cell(position) {
+            let max_mines = settings
+                .max_mines_per_cell()
+                .min(settings.mine_count - mines_placed);
+            mines[index] = rng.random_range(1..=max_mines);
+            mines_placed += mines[index];
+        }
+    }
+
+    mines
+}
+
+pub fn count_adjacent_mines(mines: &[u8], position: Position, settings: &BoardSettings) -> u8 {
+    neighbours(position, settings)
+        .into_iter()
+        .map(|adjacent_pos| mines[tile_index(adjacent_pos, settings)])
+        .sum()
+}
+
+/// Board value (3BV): the fewest left clicks that clear the board, one per opening plus one per
+/// numbered cell no opening uncovers. Cells already open count for nothing.
+pub fn three_bv(mines: &[u8], revealed: &[bool], settings: &BoardSettings) -> u16 {
+    let mut covered = revealed.to_vec();
+    let mut openings = 0;
+
+    for index in 0..mines.len() {
+        let position = tile_position(index, settings);
+        if covered[index]
+            || mines[index] > 0
+            || !settings.has_cell(position)
+            || count_adjacent_mines(mines, position, settings) > 0
+        {
+            continue;
+        }
+
+        openings += 1;
+        covered[index] = true;
+        let mut stack = vec![position];
+        while let Some(position) = stack.pop() {
+            if count_adjacent_mines(mines, position, settings) > 0 {
+                continue;
+            }
+            for adjacent_pos in neighbours(position, settings) {
+                let adjacent_index = tile_index(adjacent_pos, settings);
+                if !covered[adjacent_index] {
+                    covered[adjacent_index] = true;
+                    stack.push(adjacent_pos);
+                }
+            }
+        }
+    }
+
+    let isolated = (0..mines.len())
+        .filter(|index| {
+            !covered[*index]
+                && mines[*index] == 0
+                && settings.has_cell(tile_position(*index, settings))
+        })
+        .count();
+
+    (openings + isolated) as u16
+}
+
+/// Blank cells a layout starts open with open their neighbours, as if they had been clicked.
+fn expand_openings(revealed: &mut [bool], mines: &[u8], settings: &BoardSettings) {
+    let mut stack: Vec<Position> = (0..revealed.len())
+        .filter(|index| revealed[*index])
+        .map(|index| tile_position(index, settings))
+        .collect();
+
+    while let Some(position) = stack.pop() {
+        if count_adjacent_mines(mines, position, settings) > 0 {
+            continue;
+        }
+
+        for adjacent_pos in neighbours(position, settings) {
+            let index = tile_index(adjacent_pos, settings);
+            if !revealed[index] && mines[index] == 0 {
+                revealed[index] = true;
+                stack.push(adjacent_pos);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board_value(rows: &[&str]) -> u16 {
+        let rows: Vec<String> = rows.iter().map(|row| row.to_string()).collect();
+        let layout = BoardLayout::from_rows("test", &rows).unwrap();
+        let settings = BoardSettings {
+            width: layout.width,
+            height: layout.height,
+            ..Default::default()
+        };
+        three_bv(&layout.mines, &layout.revealed, &settings)
+    }
+
+    #[test]
+    fn each_opening_is_one_click() {
+        assert_eq!(board_value(&["....."]), 1);
+        assert_eq!(board_value(&["..*.."]), 2);
+    }
+
+    #[test]
+    fn numbers_no_opening_reaches_are_a_click_each() {
+        assert_eq!(board_value(&["*.*"]), 1);
+        assert_eq!(board_value(&["...", ".*.", "..."]), 8);
+        assert_eq!(board_value(&["....", ".*..", "...."]), 6);
+    }
+
+    #[test]
+    fn cells_already_open_count_for_nothing() {
+        assert_eq!(board_value(&["oo*.."]), 1);
+    }
+}
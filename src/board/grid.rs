@@ -0,0 +1,33 @@
+use crate::{board::BoardSettings, tile::Position};
+
+pub fn neighbours(position: Position, settings: &BoardSettings) -> Vec<Position> {
+    settings
+        .topology()
+        .neighbours(position, settings.width, settings.height)
+        .into_iter()
+        .filter(|adjacent_pos| settings.has_cell(*adjacent_pos))
+        .collect()
+}
+
+/// Neighbours of every cell by tile index, for code that works on plain vectors.
+pub fn neighbour_table(settings: &BoardSettings) -> Vec<Vec<usize>> {
+    (0..settings.width as usize * settings.height as usize)
+        .map(|index| {
+            neighbours(tile_position(index, settings), settings)
+                .into_iter()
+                .map(|position| tile_index(position, settings))
+                .collect()
+        })
+        .collect()
+}
+
+pub fn tile_index(position: Position, settings: &BoardSettings) -> usize {
+    position.y as usize * settings.width as usize + position.x as usize
+}
+
+pub fn tile_position(index: usize, settings: &BoardSettings) -> Position {
+    Position {
+        x: (index % settings.width as usize) as u8,
+        y: (index / settings.width as usize) as u8,
+    }
+}
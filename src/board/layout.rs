@@ -0,0 +1,192 @@
+use crate::board::mask::BoardMask;
+use std::fmt;
+
+/// A fixed board instead of a generated one, stored row by row from the bottom like tile
+/// indices. Used by puzzles and boards saved from the editor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoardLayout {
+    pub width: u8,
+    pub height: u8,
+    pub mines: Vec<u8>,
+    pub revealed: Vec<bool>,
+    pub flags: Vec<u8>,
+    pub mask: Option<BoardMask>,
+}
+
+#[derive(Debug)]
+pub enum LayoutError {
+    Empty,
+    TooLarge,
+    TooManyMines,
+    NoSafeCell,
+    UnknownCell(char),
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LayoutError::Empty => write!(f, "layout has no rows"),
+            LayoutError::TooLarge => write!(f, "layout is larger than 255x255"),
+            LayoutError::TooManyMines => write!(f, "layout has more than 255 mines"),
+            LayoutError::NoSafeCell => write!(f, "layout has no safe cell"),
+            LayoutError::UnknownCell(cell) => write!(f, "unknown cell '{cell}' in layout"),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+impl BoardLayout {
+    /// Parses rows written top row first:
+    /// `.` hidden safe cell, `o` revealed safe cell, `*` mine, `2`/`3` cell with that many
+    /// mines, `F` flagged mine and `_` no cell.
+    pub fn from_rows(name: &str, rows: &[String]) -> Result<Self, LayoutError> {
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+        let height = rows.len();
+        if width == 0 {
+            return Err(LayoutError::Empty);
+        }
+        if width > u8::MAX as usize || height > u8::MAX as usize {
+            return Err(LayoutError::TooLarge);
+        }
+
+        let mut layout = Self {
+            width: width as u8,
+            height: height as u8,
+            mines: Vec::with_capacity(width * height),
+            revealed: Vec::with_capacity(width * height),
+            flags: Vec::with_capacity(width * height),
+            mask: None,
+        };
+        let mut cells = Vec::with_capacity(width * height);
+
+        for row in rows.iter().rev() {
+            let mut chars = row.chars();
+            for _ in 0..width {
+                let cell = chars.next().unwrap_or('_');
+                let (exists, mines, flags, revealed) = match cell {
+                    '.' => (true, 0, 0, false),
+                    'o' => (true, 0, 0, true),
+                    '*' => (true, 1, 0, false),
+                    '2' => (true, 2, 0, false),
+                    '3' => (true, 3, 0, false),
+                    'F' => (true, 1, 1, false),
+                    '_' => (false, 0, 0, false),
+                    _ => return Err(LayoutError::UnknownCell(cell)),
+                };
+                cells.push(exists);
+                layout.mines.push(mines);
+                layout.flags.push(flags);
+                layout.revealed.push(revealed);
+            }
+        }
+
+        if layout
+            .mines
+            .iter()
+            .map(|mines| *mines as usize)
+            .sum::<usize>()
+            > u8::MAX as usize
+        {
+            return Err(LayoutError::TooManyMines);
+        }
+        if !cells
+            .iter()
+            .zip(&layout.mines)
+            .any(|(exists, mines)| *exists && *mines == 0)
+        {
+            return Err(LayoutError::NoSafeCell);
+        }
+        if cells.contains(&false) {
+            layout.mask = Some(BoardMask {
+                name: name.to_string(),
+                width: layout.width,
+                height: layout.height,
+                cells,
+            });
+        }
+
+        Ok(layout)
+    }
+
+    /// A hidden board with no mines, holes or flags.
+    pub fn empty(width: u8, height: u8) -> Self {
+        let cells = width as usize * height as usize;
+        Self {
+            width,
+            height,
+            mines: vec![0; cells],
+            revealed: vec![false; cells],
+            flags: vec![0; cells],
+            mask: None,
+        }
+    }
+
+    /// Writes the layout back out in the format [`Self::from_rows`] reads.
+    pub fn to_rows(&self) -> Vec<String> {
+        (0..self.height as usize)
+            .rev()
+            .map(|y| {
+                (0..self.width as usize)
+                    .map(|x| {
+                        let index = y * self.width as usize + x;
+                        match self.mines[index] {
+                            _ if !self.has_cell(index) => '_',
+                            0 if self.revealed[index] => 'o',
+                            0 => '.',
+                            1 if self.flags[index] > 0 => 'F',
+                            1 => '*',
+                            2 => '2',
+                            _ => '3',
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The same board cropped or extended to `width` by `height`, anchored at the bottom left.
+    pub fn resized(&self, width: u8, height: u8) -> Self {
+        let mut layout = Self::empty(width, height);
+        let mut cells = vec![true; layout.mines.len()];
+
+        for y in 0..height.min(self.height) as usize {
+            for x in 0..width.min(self.width) as usize {
+                let from = y * self.width as usize + x;
+                let to = y * width as usize + x;
+                layout.mines[to] = self.mines[from];
+                layout.revealed[to] = self.revealed[from];
+                layout.flags[to] = self.flags[from];
+                cells[to] = self.has_cell(from);
+            }
+        }
+        layout.set_cells(cells);
+
+        layout
+    }
+
+    pub fn has_cell(&self, index: usize) -> bool {
+        self.mask.as_ref().is_none_or(|mask| mask.cells[index])
+    }
+
+    /// Replaces which cells exist, dropping the mask when every cell does.
+    pub fn set_cells(&mut self, cells: Vec<bool>) {
+        self.mask = cells.contains(&false).then(|| BoardMask {
+            name: self
+                .mask
+                .as_ref()
+                .map_or_else(String::new, |mask| mask.name.clone()),
+            width: self.width,
+            height: self.height,
+            cells,
+        });
+    }
+
+    pub fn mine_count(&self) -> u8 {
+        self.mines.iter().sum()
+    }
+}
@@ -0,0 +1,91 @@
+use crate::tile::Position;
+use std::fmt;
+
+/// Marks which cells of the bounding rectangle exist, stored row by row from the bottom like
+/// tile indices.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoardMask {
+    pub name: String,
+    pub width: u8,
+    pub height: u8,
+    pub cells: Vec<bool>,
+}
+
+#[derive(Debug)]
+pub enum MaskError {
+    TooSmall,
+    TooLarge,
+    Read(String),
+}
+
+impl fmt::Display for MaskError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MaskError::TooSmall => write!(f, "mask needs at least two cells"),
+            MaskError::TooLarge => write!(f, "mask is larger than 255x255"),
+            MaskError::Read(error) => write!(f, "could not read mask: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for MaskError {}
+
+impl BoardMask {
+    /// Parses a text template, top row first. `.` and spaces are holes, anything else is a cell.
+    pub fn from_text(name: &str, text: &str) -> Result<Self, MaskError> {
+        let rows: Vec<Vec<bool>> = text
+            .lines()
+            .map(|line| {
+                line.trim_end()
+                    .chars()
+                    .map(|c| c != '.' && c != ' ')
+                    .collect()
+            })
+            .filter(|row: &Vec<bool>| !row.is_empty())
+            .collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+        Self::from_rows(name, width, rows.len(), |x, y| {
+            rows[y].get(x).copied().unwrap_or(false)
+        })
+    }
+
+    pub(super) fn from_rows(
+        name: &str,
+        width: usize,
+        height: usize,
+        is_cell: impl Fn(usize, usize) -> bool,
+    ) -> Result<Self, MaskError> {
+        if width > u8::MAX as usize || height > u8::MAX as usize {
+            return Err(MaskError::TooLarge);
+        }
+
+        // Templates are written top row first, the board counts rows from the bottom.
+        let cells: Vec<bool> = (0..height)
+            .rev()
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| is_cell(x, y))
+            .collect();
+
+        if cells.iter().filter(|cell| **cell).count() < 2 {
+            return Err(MaskError::TooSmall);
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            width: width as u8,
+            height: height as u8,
+            cells,
+        })
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.x < self.width
+            && position.y < self.height
+            && self.cells[position.y as usize * self.width as usize + position.x as usize]
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.cells.iter().filter(|cell| **cell).count()
+    }
+}
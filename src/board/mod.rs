@@ -1,17 +1,43 @@
+pub mod formats;
+pub mod generation;
+pub mod grid;
+pub mod layout;
+pub mod mask;
+#[cfg(feature = "gui")]
 pub mod resources;
+pub mod settings;
+#[cfg(feature = "gui")]
 pub mod systems;
+pub mod topology;
 
-use crate::game::{events::NewGameEvent};
+#[cfg(feature = "gui")]
+use crate::game::events::NewGameEvent;
+#[cfg(feature = "gui")]
 use bevy::prelude::*;
+#[cfg(feature = "gui")]
 pub use resources::*;
+pub use settings::*;
+#[cfg(feature = "gui")]
 use systems::*;
 
+#[cfg(feature = "gui")]
 pub struct BoardPlugin;
 
+#[cfg(feature = "gui")]
 impl Plugin for BoardPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<BoardSettings>()
+            .init_resource::<TileImages>()
+            .insert_resource(MaskLibrary::load())
             .add_systems(Startup, setup_board)
-            .add_systems(Update, reset_board.run_if(on_event::<NewGameEvent>));
+            .add_systems(
+                Update,
+                (
+                    reset_board.run_if(on_event::<NewGameEvent>),
+                    fit_board_to_window,
+                    layout_board.run_if(resource_changed::<BoardSettings>),
+                )
+                    .chain(),
+            );
     }
 }
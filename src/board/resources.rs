@@ -1,21 +1,222 @@
-use crate::config::*;
-use bevy::prelude::*;
+use crate::{
+    board::{
+        BoardShape,
+        mask::{BoardMask, MaskError},
+    },
+    storage::data_dir,
+};
+use bevy::{
+    asset::RenderAssetUsages,
+    image::{CompressedImageFormats, ImageSampler, ImageType},
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use std::{fs, path::Path};
 
-#[derive(Resource, Debug)]
-pub struct BoardSettings {
-    pub width: u8,
-    pub height: u8,
-    pub tile_size: f32,
-    pub mine_count: u8,
+const BUILT_IN_MASKS: [(&str, &str); 3] = [
+    ("Heart", include_str!("../../assets/masks/heart.txt")),
+    ("Circle", include_str!("../../assets/masks/circle.txt")),
+    ("Ring", include_str!("../../assets/masks/ring.txt")),
+];
+
+#[derive(Resource)]
+pub struct TileImages {
+    pub square: Handle<Image>,
+    pub hex: Handle<Image>,
+    pub triangle: Handle<Image>,
+}
+
+impl TileImages {
+    pub fn get(&self, shape: BoardShape) -> Handle<Image> {
+        match shape {
+            BoardShape::Square => self.square.clone(),
+            BoardShape::Hex => self.hex.clone(),
+            BoardShape::Triangle => self.triangle.clone(),
+        }
+    }
 }
 
-impl Default for BoardSettings {
-    fn default() -> Self {
+impl FromWorld for TileImages {
+    fn from_world(world: &mut World) -> Self {
+        let square = world.resource::<AssetServer>().load("tile.png");
+        let mut images = world.resource_mut::<Assets<Image>>();
+        let hex = images.add(hex_tile_image(64));
+        let triangle = images.add(triangle_tile_image(64));
+
         Self {
-            width: BOARD_WIDTH,
-            height: BOARD_HEIGHT,
-            tile_size: TILE_SIZE,
-            mine_count: MINE_COUNT,
+            square,
+            hex,
+            triangle,
+        }
+    }
+}
+
+/// Draws a pointy-top hexagon with a darker rim, white so sprites can tint it.
+fn hex_tile_image(width: u32) -> Image {
+    let height = (width as f32 * 1.154_700_5).round() as u32;
+    let half_width = width as f32 / 2.;
+    let half_height = height as f32 / 2.;
+    let rim = 2.5;
+
+    let inside = |dx: f32, dy: f32, inset: f32| {
+        let half_width = half_width - inset;
+        let half_height = half_height - inset * 1.154_700_5;
+        dx <= half_width && dy <= half_height - dx * half_height / (2. * half_width)
+    };
+
+    tile_image(width, height, |x, y| {
+        let dx = (x - half_width).abs();
+        let dy = (y - half_height).abs();
+
+        if inside(dx, dy, rim) {
+            Some(true)
+        } else if inside(dx, dy, 0.0) {
+            Some(false)
+        } else {
+            None
+        }
+    })
+}
+
+/// Draws an upward-pointing equilateral triangle; downward cells flip the sprite.
+fn triangle_tile_image(width: u32) -> Image {
+    let height = (width as f32 * 0.866_025_4).round() as u32;
+    let half_width = width as f32 / 2.;
+    let rim = 2.5;
+
+    let inside = |dx: f32, rise: f32, inset: f32| {
+        rise >= inset
+            && dx <= (height as f32 - rise) * half_width / height as f32 - inset * 1.154_700_5
+    };
+
+    tile_image(width, height, |x, y| {
+        let dx = (x - half_width).abs();
+        let rise = height as f32 - y;
+
+        if inside(dx, rise, rim) {
+            Some(true)
+        } else if inside(dx, rise, 0.0) {
+            Some(false)
+        } else {
+            None
+        }
+    })
+}
+
+/// Rasterises a tile outline where `pixel` returns `Some(true)` for the face, `Some(false)` for
+/// the rim and `None` outside the tile.
+fn tile_image(width: u32, height: u32, pixel: impl Fn(f32, f32) -> Option<bool>) -> Image {
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let color = match pixel(x as f32 + 0.5, y as f32 + 0.5) {
+                Some(true) => [255, 255, 255, 255],
+                Some(false) => [150, 150, 150, 255],
+                None => [0, 0, 0, 0],
+            };
+            data.extend_from_slice(&color);
         }
     }
+
+    Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+/// Built-in templates followed by any `.txt` or `.png` masks in the data directory's `masks`
+/// folder.
+#[derive(Resource, Default)]
+pub struct MaskLibrary {
+    pub masks: Vec<BoardMask>,
+}
+
+impl MaskLibrary {
+    pub fn load() -> Self {
+        let mut masks: Vec<BoardMask> = BUILT_IN_MASKS
+            .iter()
+            .filter_map(|(name, text)| BoardMask::from_text(name, text).ok())
+            .collect();
+
+        if let Ok(entries) = fs::read_dir(data_dir().join("masks")) {
+            let mut paths: Vec<_> = entries.flatten().map(|entry| entry.path()).collect();
+            paths.sort();
+
+            for path in paths {
+                match load_mask_file(&path) {
+                    Some(Ok(mask)) => masks.push(mask),
+                    Some(Err(error)) => warn!("Skipping mask {}: {error}", path.display()),
+                    None => {}
+                }
+            }
+        }
+
+        Self { masks }
+    }
+
+    /// The mask after `current` in the cycle, where `None` stands for the plain rectangle.
+    pub fn next(&self, current: Option<&BoardMask>) -> Option<BoardMask> {
+        let index = match current {
+            None => 0,
+            Some(mask) => self
+                .masks
+                .iter()
+                .position(|candidate| candidate.name == mask.name)
+                .map_or(0, |index| index + 1),
+        };
+
+        self.masks.get(index).cloned()
+    }
+}
+
+fn load_mask_file(path: &Path) -> Option<Result<BoardMask, MaskError>> {
+    let name = path.file_stem()?.to_string_lossy();
+
+    match path.extension()?.to_str()? {
+        "txt" => Some(
+            fs::read_to_string(path)
+                .map_err(|error| MaskError::Read(error.to_string()))
+                .and_then(|text| BoardMask::from_text(&name, &text)),
+        ),
+        "png" => Some(
+            fs::read(path)
+                .map_err(|error| MaskError::Read(error.to_string()))
+                .and_then(|bytes| BoardMask::from_png(&name, &bytes)),
+        ),
+        _ => None,
+    }
+}
+
+// Decoding images needs bevy, so reading masks from PNG lives with the window's resources.
+impl BoardMask {
+    /// Reads one cell per pixel; opaque dark pixels are cells, light or transparent ones holes.
+    pub fn from_png(name: &str, bytes: &[u8]) -> Result<Self, MaskError> {
+        let image = Image::from_buffer(
+            bytes,
+            ImageType::Extension("png"),
+            CompressedImageFormats::NONE,
+            true,
+            ImageSampler::Default,
+            RenderAssetUsages::MAIN_WORLD,
+        )
+        .map_err(|error| MaskError::Read(error.to_string()))?;
+
+        Self::from_rows(
+            name,
+            image.width() as usize,
+            image.height() as usize,
+            |x, y| {
+                image
+                    .get_color_at(x as u32, y as u32)
+                    .is_ok_and(|color| color.alpha() >= 0.5 && color.luminance() < 0.5)
+            },
+        )
+    }
 }
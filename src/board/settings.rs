@@ -0,0 +1,210 @@
+use crate::{
+    board::{
+        layout::BoardLayout,
+        mask::BoardMask,
+        topology::{self, Topology, TopologyKind},
+    },
+    config::*,
+    tile::Position,
+};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum BoardShape {
+    #[default]
+    Square,
+    Hex,
+    Triangle,
+}
+
+impl BoardShape {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BoardShape::Square => "Square",
+            BoardShape::Hex => "Hex",
+            BoardShape::Triangle => "Triangle",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            BoardShape::Square => BoardShape::Hex,
+            BoardShape::Hex => BoardShape::Triangle,
+            BoardShape::Triangle => BoardShape::Square,
+        }
+    }
+}
+
+/// The classic board sizes, plus this game's own default.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Preset {
+    Default,
+    Beginner,
+    Intermediate,
+    Expert,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 4] = [
+        Preset::Default,
+        Preset::Beginner,
+        Preset::Intermediate,
+        Preset::Expert,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Preset::Default => "default",
+            Preset::Beginner => "beginner",
+            Preset::Intermediate => "intermediate",
+            Preset::Expert => "expert",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn settings(&self) -> BoardSettings {
+        let (width, height, mine_count) = match self {
+            Preset::Default => (BOARD_WIDTH, BOARD_HEIGHT, MINE_COUNT),
+            Preset::Beginner => (9, 9, 10),
+            Preset::Intermediate => (16, 16, 40),
+            Preset::Expert => (30, 16, 99),
+        };
+        BoardSettings {
+            width,
+            height,
+            mine_count,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg_attr(feature = "gui", derive(bevy::prelude::Resource))]
+#[derive(Clone, Debug)]
+pub struct BoardSettings {
+    pub width: u8,
+    pub height: u8,
+    pub tile_size: f32,
+    pub mine_count: u8,
+    pub shape: BoardShape,
+    pub topology: TopologyKind,
+    pub mask: Option<BoardMask>,
+    pub multi_mine: bool,
+    /// Fixes the mine layout, e.g. for the daily board; random when unset.
+    pub seed: Option<u64>,
+    /// Spawns this exact board instead of generating mines.
+    pub layout: Option<BoardLayout>,
+}
+
+impl Default for BoardSettings {
+    fn default() -> Self {
+        Self {
+            width: BOARD_WIDTH,
+            height: BOARD_HEIGHT,
+            tile_size: TILE_SIZE,
+            mine_count: MINE_COUNT,
+            shape: BoardShape::Square,
+            topology: TopologyKind::Standard,
+            mask: None,
+            multi_mine: false,
+            seed: None,
+            layout: None,
+        }
+    }
+}
+
+impl BoardSettings {
+    pub fn topology(&self) -> &'static dyn Topology {
+        match self.shape {
+            BoardShape::Square => self.topology.topology(),
+            BoardShape::Hex => &topology::Hex,
+            BoardShape::Triangle => &topology::Triangle,
+        }
+    }
+
+    /// Multi-mine boards hide up to three mines per cell, each needing its own flag.
+    pub fn max_mines_per_cell(&self) -> u8 {
+        if self.multi_mine { 3 } else { 1 }
+    }
+
+    pub fn has_cell(&self, position: Position) -> bool {
+        self.mask
+            .as_ref()
+            .is_none_or(|mask| mask.contains(position))
+    }
+
+    /// Checks the board can be dealt: it has cells, and room for its mines with one to spare.
+    pub fn validate(&self) -> Result<(), String> {
+        let cells = match &self.mask {
+            Some(mask) => mask.cell_count(),
+            None => self.width as usize * self.height as usize,
+        };
+        if cells == 0 {
+            return Err("the board needs at least one cell".to_string());
+        }
+        if self.mine_count as usize >= cells {
+            return Err(format!(
+                "{} mines do not fit in {cells} cells",
+                self.mine_count
+            ));
+        }
+        Ok(())
+    }
+
+    /// Takes the size, mines and mask from `layout`, with every other setting at its default.
+    pub fn apply_layout(&mut self, layout: BoardLayout) {
+        *self = Self {
+            width: layout.width,
+            height: layout.height,
+            tile_size: self.tile_size,
+            mine_count: layout.mine_count(),
+            mask: layout.mask.clone(),
+            multi_mine: layout.mines.iter().any(|mines| *mines > 1),
+            layout: Some(layout),
+            ..Default::default()
+        };
+    }
+
+    /// Switches to `mask`'s bounds, or back to the default rectangle, keeping the default mine
+    /// density; a fixed layout no longer fits and is dropped.
+    pub fn set_mask(&mut self, mask: Option<BoardMask>) {
+        match &mask {
+            Some(mask) => {
+                let density = MINE_COUNT as f32 / (BOARD_WIDTH as f32 * BOARD_HEIGHT as f32);
+                let mine_count = (mask.cell_count() as f32 * density).round() as usize;
+
+                self.width = mask.width;
+                self.height = mask.height;
+                self.mine_count = mine_count
+                    .clamp(1, mask.cell_count() - 1)
+                    .min(u8::MAX as usize) as u8;
+            }
+            None => {
+                self.width = BOARD_WIDTH;
+                self.height = BOARD_HEIGHT;
+                self.mine_count = MINE_COUNT;
+            }
+        }
+        self.mask = mask;
+        self.layout = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_new_mask_drops_a_fixed_layout() {
+        let mut settings = BoardSettings::default();
+        settings.apply_layout(BoardLayout::empty(9, 9));
+
+        let mask = BoardMask::from_text("Plus", ".#.\n###\n.#.").unwrap();
+        settings.set_mask(Some(mask));
+        assert_eq!((settings.width, settings.height), (3, 3));
+        assert_eq!(settings.layout, None);
+    }
+}
@@ -1,12 +1,20 @@
 use crate::{
-    board::BoardSettings,
-    config::MINE_COUNT_TEXT_SIZE,
+    animation::TileAnimation,
+    board::{
+        BoardSettings, TileImages,
+        generation::{count_adjacent_mines, deal_board, three_bv},
+        grid::tile_index,
+        layout::BoardLayout,
+    },
+    config::{MINE_COUNT_TEXT_SIZE, TILE_SIZE, WINDOW_PADDING},
     game::GameStats,
-    tile::{Position, Tile, TileBundle, TileSprite, TileText, TileTextBundle},
-    utils::{calculate_tile_x, calculate_tile_y},
+    hud::{HudText, systems::HUD_TOP},
+    tile::{
+        Position, Tile, TileBundle, TileSprite, TileText, TileTextBundle, colors::number_color,
+    },
+    utils::{board_extent, tile_flipped, tile_sprite_size, tile_translation},
 };
 use bevy::{color::palettes::css::BLACK, prelude::*};
-use rand::{prelude::*, rng};
 
 #[derive(Component)]
 pub struct BoardBackground;
@@ -15,19 +23,25 @@ pub fn setup_board(
     mut commands: Commands,
     settings: Res<BoardSettings>,
     mut game_stats: ResMut<GameStats>,
+    tile_images: Res<TileImages>,
     asset_server: Res<AssetServer>,
 ) {
     render_board_bg(&mut commands, &settings);
-    spawn_tiles(&mut commands, &settings, asset_server);
-    *game_stats = GameStats::default();
+    let three_bv = spawn_tiles(&mut commands, &settings, &tile_images, asset_server);
+    *game_stats = GameStats {
+        three_bv,
+        ..GameStats::for_board(&settings)
+    };
 }
 
+#[allow(clippy::type_complexity)]
 pub fn reset_board(
     mut commands: Commands,
     settings: Res<BoardSettings>,
     mut game_stats: ResMut<GameStats>,
     bg_entities: Query<Entity, With<BoardBackground>>,
     tile_entities: Query<Entity, Or<(With<TileSprite>, With<TileText>)>>,
+    tile_images: Res<TileImages>,
     asset_server: Res<AssetServer>,
 ) {
     for entity in tile_entities.iter() {
@@ -38,60 +52,161 @@ pub fn reset_board(
     }
 
     render_board_bg(&mut commands, &settings);
-    spawn_tiles(&mut commands, &settings, asset_server);
-    *game_stats = GameStats::default();
+    let three_bv = spawn_tiles(&mut commands, &settings, &tile_images, asset_server);
+    *game_stats = GameStats {
+        three_bv,
+        ..GameStats::for_board(&settings)
+    };
+}
+
+/// Sizes the tiles to fill the window below the HUD, and moves the camera so the board is
+/// centred in that space.
+pub fn fit_board_to_window(
+    window: Single<&Window>,
+    hud: Query<&ComputedNode, With<HudText>>,
+    mut cameras: Query<&mut Transform, With<Camera2d>>,
+    mut settings: ResMut<BoardSettings>,
+) {
+    let hud_height = hud.single().map_or(0.0, |node| {
+        HUD_TOP + node.size().y * node.inverse_scale_factor()
+    });
+    let available_width = (window.width() - WINDOW_PADDING).max(0.0);
+    let available_height = (window.height() - hud_height - WINDOW_PADDING).max(0.0);
+
+    for mut transform in cameras.iter_mut() {
+        let offset = hud_height / 2.0;
+        if transform.translation.y != offset {
+            transform.translation.y = offset;
+        }
+    }
+
+    let extent = board_extent(&settings);
+    let tile_size = (available_width / extent.x)
+        .min(available_height / extent.y)
+        .floor()
+        .max(1.0);
+
+    if settings.tile_size != tile_size {
+        settings.tile_size = tile_size;
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn layout_board(
+    settings: Res<BoardSettings>,
+    mut tile_query: Query<
+        (
+            &Position,
+            &mut Transform,
+            Option<&mut Sprite>,
+            Option<&mut TextFont>,
+        ),
+        Or<(With<TileSprite>, With<TileText>)>,
+    >,
+    mut bg_query: Query<
+        &mut Sprite,
+        (
+            With<BoardBackground>,
+            Without<TileSprite>,
+            Without<TileText>,
+        ),
+    >,
+) {
+    for (position, mut transform, sprite, font) in tile_query.iter_mut() {
+        let translation = tile_translation(*position, &settings);
+        transform.translation.x = translation.x;
+        transform.translation.y = translation.y;
+
+        if let Some(mut sprite) = sprite {
+            sprite.custom_size = Some(tile_sprite_size(&settings));
+        }
+        if let Some(mut font) = font {
+            font.font_size = mine_count_text_size(settings.tile_size);
+        }
+    }
+
+    for mut sprite in bg_query.iter_mut() {
+        sprite.custom_size = Some(board_extent(&settings) * settings.tile_size);
+    }
+}
+
+pub fn mine_count_text_size(tile_size: f32) -> f32 {
+    MINE_COUNT_TEXT_SIZE * tile_size / TILE_SIZE
 }
 
 fn render_board_bg(commands: &mut Commands, settings: &BoardSettings) {
-    let total_width = settings.width as f32 * settings.tile_size;
-    let total_height = settings.height as f32 * settings.tile_size;
+    // Masked boards show their outline instead of a filled rectangle.
+    if settings.mask.is_some() {
+        return;
+    }
+
+    let total_size = board_extent(settings) * settings.tile_size;
 
     commands.spawn((
-        Sprite::from_color(BLACK, Vec2::new(total_width, total_height)),
+        Sprite::from_color(BLACK, total_size),
         Transform::from_translation(Vec3::new(0.0, 0.0, -2.0)),
         BoardBackground,
     ));
 }
 
-fn spawn_tiles(commands: &mut Commands, settings: &BoardSettings, asset_server: Res<AssetServer>) {
-    let mines = generate_mines(settings);
+/// Spawns the board and returns its 3BV.
+fn spawn_tiles(
+    commands: &mut Commands,
+    settings: &BoardSettings,
+    tile_images: &TileImages,
+    asset_server: Res<AssetServer>,
+) -> u16 {
+    let BoardLayout {
+        mines,
+        revealed,
+        flags,
+        ..
+    } = deal_board(settings);
 
     for y in 0..settings.height {
         for x in 0..settings.width {
             let position = Position { x, y };
-            let index = (y * settings.width + x) as usize;
-            let is_mine = mines[index];
+            if !settings.has_cell(position) {
+                continue;
+            }
 
-            let adjacent_mines = if is_mine {
+            let index = tile_index(position, settings);
+            let mine_count = mines[index];
+
+            let adjacent_mines = if mine_count > 0 {
                 0
             } else {
-                count_adjacent_mines(&mines, settings.width, settings.height, x, y)
+                count_adjacent_mines(&mines, position, settings)
             };
 
             let tile = Tile {
-                is_mine,
-                is_revealed: false,
-                is_flagged: false,
+                mines: mine_count,
+                flags: flags[index],
+                is_revealed: revealed[index],
+                is_exploded: false,
                 adjacent_mines,
             };
 
-            let tile_x = calculate_tile_x(position.x, settings.width, settings.tile_size);
-            let tile_y = calculate_tile_y(position.y, settings.height, settings.tile_size);
+            let translation = tile_translation(position, settings);
+            let (tile_x, tile_y) = (translation.x, translation.y);
 
             commands.spawn(TileBundle {
                 sprite: Sprite {
-                    image: asset_server.load("tile.png"),
-                    custom_size: Some(Vec2::new(settings.tile_size, settings.tile_size)),
+                    image: tile_images.get(settings.shape),
+                    custom_size: Some(tile_sprite_size(settings)),
+                    flip_y: tile_flipped(position, settings),
                     ..default()
                 },
                 position,
                 tile: tile.clone(),
                 tile_sprite: TileSprite,
+                animation: TileAnimation::default(),
                 transform: Transform::from_translation(Vec3::new(tile_x, tile_y, 0.0)),
             });
 
-            if !is_mine && adjacent_mines > 0 {
-                let text_color = get_color_from_mine_count(adjacent_mines);
+            // Multi-mine cells also label flag and mine counts, so every cell gets a text.
+            if settings.multi_mine || (mine_count == 0 && adjacent_mines > 0) {
+                let text_color = Color::from(number_color(adjacent_mines));
 
                 commands.spawn(TileTextBundle {
                     position,
@@ -100,7 +215,7 @@ fn spawn_tiles(commands: &mut Commands, settings: &BoardSettings, asset_server:
                     text: Text2d::new(adjacent_mines.to_string()),
                     font: TextFont {
                         font: asset_server.load("mine-sweeper.otf"),
-                        font_size: MINE_COUNT_TEXT_SIZE,
+                        font_size: mine_count_text_size(settings.tile_size),
                         ..default()
                     },
                     text_layout: TextLayout::new_with_justify(JustifyText::Center),
@@ -109,55 +224,23 @@ fn spawn_tiles(commands: &mut Commands, settings: &BoardSettings, asset_server:
             }
         }
     }
-}
 
-fn get_color_from_mine_count(mines: u8) -> Color {
-    match mines {
-        1 => Color::srgb(0.0, 0.0, 1.0), // Blue
-        2 => Color::srgb(0.0, 0.5, 0.0), // Green
-        3 => Color::srgb(1.0, 0.0, 0.0), // Red
-        4 => Color::srgb(0.5, 0.0, 0.5), // Purple
-        5 => Color::srgb(0.5, 0.0, 0.0), // Dark Red
-        6 => Color::srgb(0.0, 0.5, 0.5), // Teal
-        7 => Color::srgb(0.0, 0.0, 0.0), // Black
-        8 => Color::srgb(0.5, 0.5, 0.5), // Gray
-        _ => Color::srgb(0.0, 0.0, 0.0),
-    }
+    three_bv(&mines, &revealed, settings)
 }
 
-fn generate_mines(settings: &BoardSettings) -> Vec<bool> {
-    let mut rng = rng();
-    let total_tiles = settings.width as usize * settings.height as usize;
-    let mut mines = vec![false; total_tiles];
-
-    let mut mines_placed = 0;
-    while mines_placed < settings.mine_count {
-        let index = rng.random_range(0..total_tiles);
-        if !mines[index] {
-            mines[index] = true;
-            mines_placed += 1;
-        }
+/// The mines and holes of the board in play, with a fixed board's starting reveals and flags.
+pub fn current_layout<'a>(
+    settings: &BoardSettings,
+    tiles: impl Iterator<Item = (&'a Position, &'a Tile)>,
+) -> BoardLayout {
+    let mut layout = BoardLayout::empty(settings.width, settings.height);
+    for (position, tile) in tiles {
+        layout.mines[tile_index(*position, settings)] = tile.mines;
     }
-
-    mines
-}
-
-fn count_adjacent_mines(mines: &[bool], width: u8, height: u8, x: u8, y: u8) -> u8 {
-    let mut count = 0;
-    for dy in -1..=1i8 {
-        for dx in -1..=1i8 {
-            if dx == 0 && dy == 0 {
-                continue;
-            }
-            let temp_x = x as i8 + dx;
-            let temp_y = y as i8 + dy;
-            if temp_x >= 0 && temp_y >= 0 && temp_x < width as i8 && temp_y < height as i8 {
-                let index = temp_y as usize * width as usize + temp_x as usize;
-                if mines[index] {
-                    count += 1;
-                }
-            }
-        }
+    if let Some(fixed) = &settings.layout {
+        layout.revealed = fixed.revealed.clone();
+        layout.flags = fixed.flags.clone();
     }
-    count
+    layout.mask = settings.mask.clone();
+    layout
 }
@@ -0,0 +1,287 @@
+use crate::tile::Position;
+
+/// Decides which cells count as neighbours for mine numbers, flood fill and chording.
+pub trait Topology: Send + Sync {
+    fn neighbours(&self, position: Position, width: u8, height: u8) -> Vec<Position>;
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum TopologyKind {
+    #[default]
+    Standard,
+    Torus,
+    Knight,
+    Radius2,
+    Orthogonal,
+}
+
+impl TopologyKind {
+    pub const ALL: [TopologyKind; 5] = [
+        TopologyKind::Standard,
+        TopologyKind::Torus,
+        TopologyKind::Knight,
+        TopologyKind::Radius2,
+        TopologyKind::Orthogonal,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TopologyKind::Standard => "Standard",
+            TopologyKind::Torus => "Torus",
+            TopologyKind::Knight => "Knight",
+            TopologyKind::Radius2 => "Radius 2",
+            TopologyKind::Orthogonal => "Orthogonal",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|kind| kind == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn topology(&self) -> &'static dyn Topology {
+        match self {
+            TopologyKind::Standard => &Standard,
+            TopologyKind::Torus => &Torus,
+            TopologyKind::Knight => &Knight,
+            TopologyKind::Radius2 => &Radius2,
+            TopologyKind::Orthogonal => &Orthogonal,
+        }
+    }
+}
+
+const STANDARD_OFFSETS: [(i16, i16); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+const KNIGHT_OFFSETS: [(i16, i16); 8] = [
+    (-2, -1),
+    (-1, -2),
+    (1, -2),
+    (2, -1),
+    (-2, 1),
+    (-1, 2),
+    (1, 2),
+    (2, 1),
+];
+
+const ORTHOGONAL_OFFSETS: [(i16, i16); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+// Odd rows of the hex grid are shifted half a tile to the right.
+const HEX_EVEN_ROW_OFFSETS: [(i16, i16); 6] = [(-1, 0), (1, 0), (-1, -1), (0, -1), (-1, 1), (0, 1)];
+const HEX_ODD_ROW_OFFSETS: [(i16, i16); 6] = [(-1, 0), (1, 0), (0, -1), (1, -1), (0, 1), (1, 1)];
+
+// Triangles point up when x + y is even. Cells touching any corner are neighbours:
+// four along the row, five across the shared edge and three beyond the apex.
+const TRIANGLE_UP_OFFSETS: [(i16, i16); 12] = [
+    (-2, 0),
+    (-1, 0),
+    (1, 0),
+    (2, 0),
+    (-2, -1),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (2, -1),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+const TRIANGLE_DOWN_OFFSETS: [(i16, i16); 12] = [
+    (-2, 0),
+    (-1, 0),
+    (1, 0),
+    (2, 0),
+    (-2, 1),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (2, 1),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+pub struct Standard;
+
+impl Topology for Standard {
+    fn neighbours(&self, position: Position, width: u8, height: u8) -> Vec<Position> {
+        bounded_neighbours(position, &STANDARD_OFFSETS, width, height)
+    }
+}
+
+pub struct Torus;
+
+impl Topology for Torus {
+    fn neighbours(&self, position: Position, width: u8, height: u8) -> Vec<Position> {
+        let mut neighbours = Vec::with_capacity(STANDARD_OFFSETS.len());
+
+        for (dx, dy) in STANDARD_OFFSETS {
+            let adjacent_pos = Position {
+                x: (position.x as i16 + dx).rem_euclid(width as i16) as u8,
+                y: (position.y as i16 + dy).rem_euclid(height as i16) as u8,
+            };
+
+            // Boards narrower than three cells wrap onto themselves.
+            if adjacent_pos != position && !neighbours.contains(&adjacent_pos) {
+                neighbours.push(adjacent_pos);
+            }
+        }
+
+        neighbours
+    }
+}
+
+pub struct Knight;
+
+impl Topology for Knight {
+    fn neighbours(&self, position: Position, width: u8, height: u8) -> Vec<Position> {
+        bounded_neighbours(position, &KNIGHT_OFFSETS, width, height)
+    }
+}
+
+pub struct Radius2;
+
+impl Topology for Radius2 {
+    fn neighbours(&self, position: Position, width: u8, height: u8) -> Vec<Position> {
+        let offsets: Vec<(i16, i16)> = (-2..=2)
+            .flat_map(|dy| (-2..=2).map(move |dx| (dx, dy)))
+            .filter(|offset| *offset != (0, 0))
+            .collect();
+
+        bounded_neighbours(position, &offsets, width, height)
+    }
+}
+
+pub struct Orthogonal;
+
+impl Topology for Orthogonal {
+    fn neighbours(&self, position: Position, width: u8, height: u8) -> Vec<Position> {
+        bounded_neighbours(position, &ORTHOGONAL_OFFSETS, width, height)
+    }
+}
+
+pub struct Hex;
+
+impl Topology for Hex {
+    fn neighbours(&self, position: Position, width: u8, height: u8) -> Vec<Position> {
+        let offsets = if position.y.is_multiple_of(2) {
+            &HEX_EVEN_ROW_OFFSETS
+        } else {
+            &HEX_ODD_ROW_OFFSETS
+        };
+
+        bounded_neighbours(position, offsets, width, height)
+    }
+}
+
+pub struct Triangle;
+
+impl Triangle {
+    pub fn points_up(position: Position) -> bool {
+        (position.x as u16 + position.y as u16).is_multiple_of(2)
+    }
+}
+
+impl Topology for Triangle {
+    fn neighbours(&self, position: Position, width: u8, height: u8) -> Vec<Position> {
+        let offsets = if Triangle::points_up(position) {
+            &TRIANGLE_UP_OFFSETS
+        } else {
+            &TRIANGLE_DOWN_OFFSETS
+        };
+
+        bounded_neighbours(position, offsets, width, height)
+    }
+}
+
+fn bounded_neighbours(
+    position: Position,
+    offsets: &[(i16, i16)],
+    width: u8,
+    height: u8,
+) -> Vec<Position> {
+    offsets
+        .iter()
+        .filter_map(|(dx, dy)| {
+            let new_x = position.x as i16 + dx;
+            let new_y = position.y as i16 + dy;
+
+            (new_x >= 0 && new_y >= 0 && new_x < width as i16 && new_y < height as i16).then_some(
+                Position {
+                    x: new_x as u8,
+                    y: new_y as u8,
+                },
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(topology: &dyn Topology, x: u8, y: u8) -> usize {
+        topology.neighbours(Position { x, y }, 7, 7).len()
+    }
+
+    #[test]
+    fn neighbour_counts_match_each_topology() {
+        assert_eq!((count(&Standard, 3, 3), count(&Standard, 0, 0)), (8, 3));
+        assert_eq!((count(&Torus, 3, 3), count(&Torus, 0, 0)), (8, 8));
+        assert_eq!((count(&Knight, 3, 3), count(&Knight, 0, 0)), (8, 2));
+        assert_eq!((count(&Radius2, 3, 3), count(&Radius2, 0, 0)), (24, 8));
+        assert_eq!((count(&Orthogonal, 3, 3), count(&Orthogonal, 0, 0)), (4, 2));
+        assert_eq!((count(&Hex, 3, 3), count(&Hex, 3, 2)), (6, 6));
+        assert_eq!((count(&Triangle, 3, 3), count(&Triangle, 3, 2)), (12, 12));
+    }
+
+    #[test]
+    fn the_torus_wraps_around_the_edges() {
+        let neighbours = Torus.neighbours(Position { x: 0, y: 0 }, 7, 7);
+        assert!(neighbours.contains(&Position { x: 6, y: 6 }));
+        assert!(neighbours.contains(&Position { x: 1, y: 6 }));
+
+        // Two cells wide, left and right are the same cell and count once.
+        let narrow = Torus.neighbours(Position { x: 0, y: 0 }, 2, 3);
+        assert_eq!(narrow.len(), 5);
+    }
+
+    #[test]
+    fn neighbours_are_mutual() {
+        let topologies: [&dyn Topology; 7] = [
+            &Standard,
+            &Torus,
+            &Knight,
+            &Radius2,
+            &Orthogonal,
+            &Hex,
+            &Triangle,
+        ];
+        for topology in topologies {
+            for (width, height) in [(7, 7), (2, 3), (6, 5)] {
+                for y in 0..height {
+                    for x in 0..width {
+                        let position = Position { x, y };
+                        for neighbour in topology.neighbours(position, width, height) {
+                            assert_ne!(neighbour, position);
+                            assert!(
+                                topology
+                                    .neighbours(neighbour, width, height)
+                                    .contains(&position),
+                                "{neighbour:?} does not see {position:?} back"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,360 @@
+//! Lets programs play by the same rules as the window: in process through [`Bot`], or as a
+//! separate program speaking one JSON object per line.
+
+use crate::{
+    board::{
+        BoardSettings,
+        grid::{neighbour_table, tile_index},
+    },
+    engine::{Engine, Outcome},
+    solver::{Cell, Planner, SolverBoard},
+    tile::Position,
+};
+use rand::{prelude::*, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+/// A move, written `{"action": "reveal", "x": 3, "y": 0}`; `y` counts rows from the bottom.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum Action {
+    Reveal { x: u8, y: u8 },
+    Flag { x: u8, y: u8 },
+    Chord { x: u8, y: u8 },
+}
+
+impl Action {
+    pub fn position(&self) -> Position {
+        let (Action::Reveal { x, y } | Action::Flag { x, y } | Action::Chord { x, y }) = *self;
+        Position { x, y }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum RuleError {
+    GameOver,
+    OffBoard,
+    AlreadyOpen,
+    Flagged,
+    NotOpen,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleError::GameOver => write!(f, "the game is over"),
+            RuleError::OffBoard => write!(f, "there is no cell there"),
+            RuleError::AlreadyOpen => write!(f, "the cell is already open"),
+            RuleError::Flagged => write!(f, "the cell is flagged"),
+            RuleError::NotOpen => write!(f, "only open cells can be chorded"),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+/// The board as a player sees it; hidden mines are never included.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct View {
+    pub width: u8,
+    pub height: u8,
+    pub mines: u8,
+    pub mines_left: i16,
+    pub max_mines_per_cell: u8,
+    pub topology: String,
+    pub outcome: Outcome,
+    pub moves: u32,
+    /// `cells[y][x]`, bottom row first: `#` hidden, `F` flagged (`F2`, `F3` with more
+    /// flags), the number of an open cell, `*` a mine once the game is over, `X` the mine
+    /// that ended it, and an empty string where there is no cell.
+    pub cells: Vec<Vec<String>>,
+}
+
+/// What a move did: whether it changed the board, or why it broke the rules.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Reply {
+    pub ok: bool,
+    pub changed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub view: View,
+}
+
+/// One board being played by a bot. Every move counts, including illegal ones, so a bot that
+/// keeps breaking the rules still runs out of moves.
+#[derive(Clone, Debug)]
+pub struct Session {
+    pub engine: Engine,
+    pub moves: u32,
+}
+
+impl Session {
+    pub fn new(settings: &BoardSettings) -> Self {
+        Self {
+            engine: Engine::new(settings),
+            moves: 0,
+        }
+    }
+
+    pub fn outcome(&self) -> Outcome {
+        self.engine.outcome
+    }
+
+    /// Applies `action`; returns whether it changed the board.
+    pub fn apply(&mut self, action: Action) -> Result<bool, RuleError> {
+        self.moves += 1;
+        if self.engine.outcome != Outcome::Playing {
+            return Err(RuleError::GameOver);
+        }
+
+        let settings = &self.engine.settings;
+        let position = action.position();
+        if position.x >= settings.width
+            || position.y >= settings.height
+            || !settings.has_cell(position)
+        {
+            return Err(RuleError::OffBoard);
+        }
+
+        let cell = tile_index(position, settings);
+        let revealed = self.engine.is_revealed(cell);
+        match action {
+            Action::Reveal { .. } if revealed => Err(RuleError::AlreadyOpen),
+            Action::Reveal { .. } if self.engine.flags(cell) > 0 => Err(RuleError::Flagged),
+            Action::Reveal { .. } => Ok(self.engine.reveal(cell)),
+            Action::Flag { .. } if revealed => Err(RuleError::AlreadyOpen),
+            Action::Flag { .. } => Ok(self.engine.flag(cell)),
+            Action::Chord { .. } if !revealed => Err(RuleError::NotOpen),
+            Action::Chord { .. } => Ok(self.engine.chord(cell)),
+        }
+    }
+
+    /// Applies `action` and describes the result.
+    pub fn reply(&mut self, action: Action) -> Reply {
+        let result = self.apply(action);
+        Reply {
+            ok: result.is_ok(),
+            changed: result == Ok(true),
+            error: result.err().map(|err| err.to_string()),
+            view: self.view(),
+        }
+    }
+
+    pub fn view(&self) -> View {
+        let engine = &self.engine;
+        let settings = &engine.settings;
+        let cells = (0..settings.height)
+            .map(|y| {
+                (0..settings.width)
+                    .map(|x| self.cell_label(tile_index(Position { x, y }, settings)))
+                    .collect()
+            })
+            .collect();
+
+        View {
+            width: settings.width,
+            height: settings.height,
+            mines: settings.mine_count,
+            mines_left: engine.board.mines_left(),
+            max_mines_per_cell: settings.max_mines_per_cell(),
+            topology: settings.topology.name().to_string(),
+            outcome: engine.outcome,
+            moves: self.moves,
+            cells,
+        }
+    }
+
+    fn cell_label(&self, cell: usize) -> String {
+        let Some(tile) = self.engine.board.tile(cell) else {
+            return String::new();
+        };
+        // A mine claimed by a spare life stays a flag until the game is over.
+        match tile.flags {
+            _ if tile.is_revealed && tile.is_mine() && self.engine.outcome != Outcome::Playing => {
+                if self.engine.exploded == Some(cell) {
+                    "X"
+                } else {
+                    "*"
+                }
+                .to_string()
+            }
+            _ if tile.is_revealed && !tile.is_mine() => tile.adjacent_mines.to_string(),
+            0 => "#".to_string(),
+            1 => "F".to_string(),
+            flags => format!("F{flags}"),
+        }
+    }
+}
+
+/// A player driven by code. It only ever sees the [`View`].
+///
+/// Every bot plays the sequence `minesweeper-bot serve` speaks: it is shown the opening
+/// [`View`], answers with an [`Action`], is sent the [`Reply`] to that move, and so on until the
+/// game ends or it gives up. [`next_action`](Bot::next_action) is handed the latest view each
+/// time, which after the first move is the one in the last reply.
+pub trait Bot {
+    fn name(&self) -> &str;
+
+    /// The next move, or `None` to give up.
+    fn next_action(&mut self, view: &View) -> io::Result<Option<Action>>;
+
+    /// Called after each move with how it went.
+    fn observe(&mut self, _reply: &Reply) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Lets `bot` play until the game ends, it gives up or it has used `max_moves`.
+pub fn play(bot: &mut dyn Bot, session: &mut Session, max_moves: u32) -> io::Result<()> {
+    while session.outcome() == Outcome::Playing && session.moves < max_moves {
+        let Some(action) = bot.next_action(&session.view())? else {
+            break;
+        };
+        bot.observe(&session.reply(action))?;
+    }
+    Ok(())
+}
+
+/// The built-in solver: plays every deduction, and guesses at random when there are none.
+pub struct SolverBot {
+    neighbours: Vec<Vec<usize>>,
+    planner: Planner,
+    rng: StdRng,
+}
+
+impl SolverBot {
+    /// A solver for boards dealt with `settings`, whose topology gives the neighbours.
+    pub fn new(settings: &BoardSettings, seed: u64) -> Self {
+        Self {
+            neighbours: neighbour_table(settings),
+            planner: Planner::default(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    fn board(&self, view: &View) -> SolverBoard {
+        let cells = view
+            .cells
+            .iter()
+            .flatten()
+            .map(|label| match label.as_str() {
+                "" => Cell::Missing,
+                "#" => Cell::Hidden,
+                "F" => Cell::Mine(1),
+                flags if flags.starts_with('F') => Cell::Mine(flags[1..].parse().unwrap_or(1)),
+                number => Cell::Revealed(number.parse().unwrap_or(0)),
+            })
+            .collect();
+
+        SolverBoard {
+            cells,
+            neighbours: self.neighbours.clone(),
+            max_mines_per_cell: view.max_mines_per_cell,
+            total_mines: view.mines as u32,
+        }
+    }
+}
+
+impl Bot for SolverBot {
+    fn name(&self) -> &str {
+        "solver"
+    }
+
+    fn next_action(&mut self, view: &View) -> io::Result<Option<Action>> {
+        let width = view.width as usize;
+        let action_at = |cell: usize, mines: u8| {
+            let (x, y) = ((cell % width) as u8, (cell / width) as u8);
+            if mines > 0 {
+                Action::Flag { x, y }
+            } else {
+                Action::Reveal { x, y }
+            }
+        };
+
+        let board = self.board(view);
+        if let Some((cell, mines)) = self.planner.next_move(&board) {
+            return Ok(Some(action_at(cell, mines)));
+        }
+
+        let hidden: Vec<usize> = (0..board.cells.len())
+            .filter(|cell| board.cells[*cell] == Cell::Hidden)
+            .collect();
+        Ok(hidden.choose(&mut self.rng).map(|cell| action_at(*cell, 0)))
+    }
+}
+
+/// A bot in another program, spoken to one JSON line at a time as `serve` does: it is sent the
+/// opening [`View`] on stdin, then answers with an [`Action`] on stdout, or an empty line to give
+/// up, and is sent the [`Reply`] after each move.
+pub struct ProcessBot {
+    name: String,
+    /// Whether the opening view has been sent.
+    started: bool,
+    child: Child,
+    input: ChildStdin,
+    output: BufReader<ChildStdout>,
+}
+
+impl ProcessBot {
+    /// Runs `command` through the shell.
+    pub fn spawn(command: &str) -> io::Result<Self> {
+        let mut child = Command::new("sh")
+            .args(["-c", command])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let input = child.stdin.take().ok_or(io::ErrorKind::BrokenPipe)?;
+        let output = BufReader::new(child.stdout.take().ok_or(io::ErrorKind::BrokenPipe)?);
+
+        Ok(Self {
+            name: command.to_string(),
+            started: false,
+            child,
+            input,
+            output,
+        })
+    }
+
+    fn send<T: Serialize>(&mut self, message: &T) -> io::Result<()> {
+        serde_json::to_writer(&mut self.input, message)?;
+        self.input.write_all(b"\n")?;
+        self.input.flush()
+    }
+}
+
+impl Bot for ProcessBot {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn next_action(&mut self, view: &View) -> io::Result<Option<Action>> {
+        // Later views reach the program in each reply.
+        if !self.started {
+            self.send(view)?;
+            self.started = true;
+        }
+        let mut line = String::new();
+        self.output.read_line(&mut line)?;
+        if line.trim().is_empty() {
+            return Ok(None);
+        }
+        serde_json::from_str(&line)
+            .map(Some)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    fn observe(&mut self, reply: &Reply) -> io::Result<()> {
+        self.send(reply)
+    }
+}
+
+impl Drop for ProcessBot {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
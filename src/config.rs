@@ -4,6 +4,10 @@ pub const MINE_COUNT: u8 = 20;
 pub const TILE_SIZE: f32 = 32.0;
 pub const MINE_COUNT_TEXT_SIZE: f32 = 15.0;
 
-const WINDOW_PADDING: f32 = 20.0;
+pub const WINDOW_PADDING: f32 = 20.0;
+/// Room for the HUD's usual few lines above the board.
+pub const HUD_HEIGHT: f32 = 64.0;
+pub const MIN_WINDOW_WIDTH: f32 = 240.0;
+pub const MIN_WINDOW_HEIGHT: f32 = 240.0;
 pub const WINDOW_WIDTH: f32 = (BOARD_WIDTH as f32 * TILE_SIZE) + WINDOW_PADDING;
-pub const WINDOW_HEIGHT: f32 = (BOARD_HEIGHT as f32 * TILE_SIZE) + WINDOW_PADDING;
+pub const WINDOW_HEIGHT: f32 = (BOARD_HEIGHT as f32 * TILE_SIZE) + WINDOW_PADDING + HUD_HEIGHT;
@@ -0,0 +1,12 @@
+use crate::net::PeerId;
+use bevy::prelude::*;
+
+/// Another player's cursor, labelled with their name.
+#[derive(Component)]
+pub struct CoopCursor {
+    pub player: PeerId,
+}
+
+/// The corner mark on a flag in the colour of whoever placed it.
+#[derive(Component)]
+pub struct FlagOwnerMark;
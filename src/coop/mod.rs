@@ -0,0 +1,78 @@
+pub mod components;
+pub mod protocol;
+pub mod resources;
+pub mod systems;
+
+use crate::{
+    accessibility::systems::handle_cursor_action,
+    autoplay::systems::run_autoplay,
+    board::{BoardSettings, systems::reset_board},
+    game::{
+        events::NewGameEvent,
+        systems::{handle_chord_tile, handle_new_game, handle_tile_flagged, handle_tile_revealed},
+    },
+    input::systems::handle_mouse_input,
+    mode::systems::{apply_daily_preset, start_run},
+    net::NetConfig,
+    replay::systems::run_playback,
+};
+use bevy::prelude::*;
+pub use components::*;
+pub use protocol::*;
+pub use resources::*;
+use systems::*;
+
+/// Several players on one board over the LAN, with the host's board the one that counts.
+pub struct CoopPlugin;
+
+impl Plugin for CoopPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NetConfig>()
+            .init_resource::<CoopNet>()
+            .init_resource::<Coop>()
+            .init_resource::<FlagOwners>()
+            .add_systems(Startup, open_coop_connection)
+            .add_systems(
+                Update,
+                (
+                    deal_coop_board
+                        .after(apply_daily_preset)
+                        .before(reset_board),
+                    apply_coop_rules.after(handle_new_game),
+                )
+                    .run_if(on_event::<NewGameEvent>),
+            )
+            .add_systems(
+                Update,
+                (
+                    receive_coop_messages.before(start_run),
+                    forward_coop_moves
+                        .run_if(coop_client)
+                        .after(handle_mouse_input)
+                        .after(handle_cursor_action)
+                        .after(run_autoplay)
+                        .after(run_playback),
+                )
+                    .before(handle_tile_revealed)
+                    .before(handle_tile_flagged)
+                    .before(handle_chord_tile),
+            )
+            .add_systems(
+                Update,
+                (
+                    // Tiles from the host may belong to a board dealt this frame.
+                    apply_coop_tiles
+                        .run_if(coop_client)
+                        .after(receive_coop_messages)
+                        .after(reset_board),
+                    send_coop_cursor,
+                    show_coop_cursors
+                        .run_if(resource_changed::<Coop>.or(resource_changed::<BoardSettings>)),
+                    show_flag_owners.run_if(
+                        resource_changed::<FlagOwners>.or(resource_changed::<BoardSettings>),
+                    ),
+                ),
+            )
+            .add_systems(PostUpdate, broadcast_coop_tiles);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{
+    bot::Action,
+    net::{PeerId, SharedBoard},
+    tile::{Position, Tile},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Player {
+    pub id: PeerId,
+    pub name: String,
+}
+
+/// A cell as the host sees it, with whoever flagged it.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TileState {
+    pub x: u8,
+    pub y: u8,
+    pub flags: u8,
+    pub revealed: bool,
+    pub exploded: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<PeerId>,
+}
+
+impl TileState {
+    pub fn new(position: Position, tile: &Tile, owner: Option<PeerId>) -> Self {
+        Self {
+            x: position.x,
+            y: position.y,
+            flags: tile.flags,
+            revealed: tile.is_revealed,
+            exploded: tile.is_exploded,
+            owner,
+        }
+    }
+
+    pub fn position(&self) -> Position {
+        Position {
+            x: self.x,
+            y: self.y,
+        }
+    }
+
+    pub fn apply(&self, tile: &mut Tile) {
+        tile.flags = self.flags;
+        tile.is_revealed = self.revealed;
+        tile.is_exploded = self.exploded;
+    }
+}
+
+/// Players send their moves and cursor to the host, which plays the moves on its board and
+/// sends everyone the tiles they changed. `Restart` asks the host for a new board.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CoopMessage {
+    Hello {
+        name: String,
+    },
+    Welcome {
+        player: PeerId,
+    },
+    Players {
+        players: Vec<Player>,
+    },
+    Start {
+        board: SharedBoard,
+    },
+    Move {
+        action: Action,
+    },
+    Tiles {
+        tiles: Vec<TileState>,
+    },
+    /// A cursor in board units, as from `world_to_board`.
+    Cursor {
+        player: PeerId,
+        x: f32,
+        y: f32,
+    },
+    Lost,
+    Restart,
+}
@@ -0,0 +1,64 @@
+use crate::{
+    coop::{CoopMessage, Player, TileState},
+    net::{Client, Host, PeerId, SharedBoard},
+    tile::Position,
+};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// Up to eight players, the host included.
+pub const MAX_COOP_PLAYERS: usize = 8;
+
+pub const PLAYER_COLORS: [Color; MAX_COOP_PLAYERS] = [
+    Color::srgb(0.95, 0.3, 0.3),
+    Color::srgb(0.3, 0.6, 1.0),
+    Color::srgb(0.3, 0.85, 0.4),
+    Color::srgb(1.0, 0.8, 0.2),
+    Color::srgb(0.8, 0.4, 1.0),
+    Color::srgb(0.2, 0.9, 0.9),
+    Color::srgb(1.0, 0.55, 0.15),
+    Color::srgb(1.0, 0.5, 0.8),
+];
+
+pub fn player_color(player: PeerId) -> Color {
+    PLAYER_COLORS[player as usize % PLAYER_COLORS.len()]
+}
+
+#[derive(Resource, Default)]
+pub enum CoopNet {
+    #[default]
+    Off,
+    Host(Host<CoopMessage>),
+    Client(Client<CoopMessage>),
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct Coop {
+    /// This player's id, once the host has assigned one.
+    pub me: Option<PeerId>,
+    pub players: Vec<Player>,
+    /// The board being played; a new one is dealt for every game.
+    pub board: Option<SharedBoard>,
+    /// Where the other players are pointing, in board units.
+    pub cursors: HashMap<PeerId, Vec2>,
+    /// Tile states from the host whose tiles have not been spawned yet.
+    pub pending: Vec<TileState>,
+    /// Flags other players placed this frame, so the host credits them and not itself.
+    pub peer_flags: Vec<(Position, PeerId)>,
+    /// Set while dealing the host's board, which is not a restart of this player's own.
+    pub dealing: bool,
+    pub status: String,
+}
+
+impl Coop {
+    pub fn name(&self, player: PeerId) -> &str {
+        self.players
+            .iter()
+            .find(|other| other.id == player)
+            .map_or("?", |other| other.name.as_str())
+    }
+}
+
+/// Who placed each flag on the board.
+#[derive(Resource, Debug, Default)]
+pub struct FlagOwners(pub HashMap<Position, PeerId>);
@@ -0,0 +1,476 @@
+use crate::{
+    board::BoardSettings,
+    bot::Action,
+    coop::*,
+    game::{GameState, GameStats, Lives, events::*},
+    mode::GameMode,
+    net::{Client, HOST, Host, NetConfig, NetEvent, NetGame, NetRole, SharedBoard},
+    tile::{Position, Tile, TileSprite},
+    utils::{board_to_world, tile_flipped, tile_sprite_size, tile_translation, world_to_board},
+};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+const CURSOR_SIZE: f32 = 10.0;
+const CURSOR_LABEL_SIZE: f32 = 12.0;
+const CURSOR_Z: f32 = 5.0;
+/// Seconds between cursor updates; enough to follow a pointer without flooding the host.
+const CURSOR_SEND_INTERVAL: f32 = 0.05;
+const FLAG_MARK_SCALE: f32 = 0.18;
+const FLAG_MARK_Z: f32 = 2.0;
+
+pub fn coop_client(net: Res<CoopNet>) -> bool {
+    matches!(*net, CoopNet::Client(_))
+}
+
+pub fn open_coop_connection(
+    config: Res<NetConfig>,
+    mut net: ResMut<CoopNet>,
+    mut coop: ResMut<Coop>,
+    mut mode: ResMut<GameMode>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+) {
+    if config.game != NetGame::Coop {
+        return;
+    }
+    match &config.role {
+        NetRole::Offline => {}
+        NetRole::Host { port } => match Host::bind(*port, MAX_COOP_PLAYERS - 1) {
+            Ok(host) => {
+                coop.me = Some(HOST);
+                coop.players = vec![Player {
+                    id: HOST,
+                    name: config.name.clone(),
+                }];
+                coop.status = format!("Hosting on port {}", host.port);
+                *net = CoopNet::Host(host);
+                // The board from startup was not dealt from a seed the others can share.
+                *mode = GameMode::Classic;
+                new_game_events.write(NewGameEvent);
+            }
+            Err(err) => coop.status = format!("Could not host on port {port}: {err}"),
+        },
+        NetRole::Join { address } => match Client::connect(address) {
+            Ok(client) => {
+                client.send(&CoopMessage::Hello {
+                    name: config.name.clone(),
+                });
+                coop.status = format!("Joined {address}");
+                *net = CoopNet::Client(client);
+            }
+            Err(err) => coop.status = format!("Could not join {address}: {err}"),
+        },
+    }
+}
+
+/// Every opened or flagged tile, for catching a player up with the board.
+fn board_snapshot(
+    tile_query: &Query<(&Position, &Tile), With<TileSprite>>,
+    flag_owners: &FlagOwners,
+) -> Vec<TileState> {
+    tile_query
+        .iter()
+        .filter(|(_, tile)| tile.is_revealed || tile.is_flagged())
+        .map(|(position, tile)| {
+            TileState::new(*position, tile, flag_owners.0.get(position).copied())
+        })
+        .collect()
+}
+
+/// The host plays the moves players send on its own board; players follow what the host sends.
+#[allow(clippy::too_many_arguments)]
+pub fn receive_coop_messages(
+    mut net: ResMut<CoopNet>,
+    mut coop: ResMut<Coop>,
+    flag_owners: Res<FlagOwners>,
+    state: Res<State<GameState>>,
+    mut mode: ResMut<GameMode>,
+    tile_query: Query<(&Position, &Tile), With<TileSprite>>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+    mut tile_revealed_events: EventWriter<TileRevealedEvent>,
+    mut tile_flagged_events: EventWriter<TileFlaggedEvent>,
+    mut chord_events: EventWriter<ChordEvent>,
+    mut game_over_events: EventWriter<GameOverEvent>,
+) {
+    let playing = *state.get() == GameState::Playing;
+    let mut host_left = false;
+    match &*net {
+        CoopNet::Off => {}
+        CoopNet::Host(host) => {
+            let mut players_changed = false;
+            for event in host.poll() {
+                match event {
+                    NetEvent::Message(id, CoopMessage::Hello { name }) => {
+                        if !coop.players.iter().any(|player| player.id == id) {
+                            coop.players.push(Player { id, name });
+                        }
+                        host.send(id, &CoopMessage::Welcome { player: id });
+                        if let Some(board) = &coop.board {
+                            host.send(
+                                id,
+                                &CoopMessage::Start {
+                                    board: board.clone(),
+                                },
+                            );
+                        }
+                        host.send(
+                            id,
+                            &CoopMessage::Tiles {
+                                tiles: board_snapshot(&tile_query, &flag_owners),
+                            },
+                        );
+                        if *state.get() == GameState::GameOver {
+                            host.send(id, &CoopMessage::Lost);
+                        }
+                        players_changed = true;
+                    }
+                    NetEvent::Message(id, CoopMessage::Move { action }) if playing => {
+                        let position = action.position();
+                        match action {
+                            Action::Reveal { .. } => {
+                                tile_revealed_events.write(TileRevealedEvent { position });
+                            }
+                            Action::Flag { .. } => {
+                                coop.peer_flags.push((position, id));
+                                tile_flagged_events.write(TileFlaggedEvent { position });
+                            }
+                            Action::Chord { .. } => {
+                                chord_events.write(ChordEvent { position });
+                            }
+                        }
+                    }
+                    NetEvent::Message(id, CoopMessage::Cursor { x, y, .. }) => {
+                        coop.cursors.insert(id, Vec2::new(x, y));
+                        host.broadcast(&CoopMessage::Cursor { player: id, x, y });
+                    }
+                    NetEvent::Message(id, CoopMessage::Restart) => {
+                        // Mid-game a restart only reset that player's copy, so catch them up.
+                        if playing {
+                            host.send(
+                                id,
+                                &CoopMessage::Tiles {
+                                    tiles: board_snapshot(&tile_query, &flag_owners),
+                                },
+                            );
+                        } else {
+                            new_game_events.write(NewGameEvent);
+                        }
+                    }
+                    NetEvent::Left(id) => {
+                        coop.players.retain(|player| player.id != id);
+                        coop.cursors.remove(&id);
+                        players_changed = true;
+                    }
+                    NetEvent::Joined(_) | NetEvent::Message(..) => {}
+                }
+            }
+            if players_changed {
+                host.broadcast(&CoopMessage::Players {
+                    players: coop.players.clone(),
+                });
+            }
+        }
+        CoopNet::Client(client) => {
+            for message in client.poll() {
+                match message {
+                    Some(CoopMessage::Welcome { player }) => coop.me = Some(player),
+                    Some(CoopMessage::Players { players }) => {
+                        coop.cursors
+                            .retain(|id, _| players.iter().any(|player| player.id == *id));
+                        coop.players = players;
+                    }
+                    Some(CoopMessage::Start { board }) => {
+                        coop.board = Some(board);
+                        coop.pending.clear();
+                        coop.dealing = true;
+                        *mode = GameMode::Classic;
+                        new_game_events.write(NewGameEvent);
+                    }
+                    Some(CoopMessage::Tiles { tiles }) => coop.pending.extend(tiles),
+                    Some(CoopMessage::Cursor { player, x, y }) if coop.me != Some(player) => {
+                        coop.cursors.insert(player, Vec2::new(x, y));
+                    }
+                    Some(CoopMessage::Lost) => {
+                        game_over_events.write(GameOverEvent);
+                    }
+                    Some(_) => {}
+                    None => host_left = true,
+                }
+            }
+        }
+    }
+
+    if host_left {
+        *net = CoopNet::Off;
+        coop.board = None;
+        coop.players.clear();
+        coop.cursors.clear();
+        coop.status = "The host has left".to_string();
+    }
+}
+
+/// The host deals a fresh board for everyone; a player's own restart asks the host for one.
+pub fn deal_coop_board(
+    net: Res<CoopNet>,
+    mut coop: ResMut<Coop>,
+    mut settings: ResMut<BoardSettings>,
+    mut flag_owners: ResMut<FlagOwners>,
+) {
+    match &*net {
+        CoopNet::Off => return,
+        CoopNet::Host(host) => {
+            let board = SharedBoard::from_settings(&settings, rand::random());
+            host.broadcast(&CoopMessage::Start {
+                board: board.clone(),
+            });
+            coop.board = Some(board);
+        }
+        CoopNet::Client(client) => {
+            if !coop.dealing {
+                client.send(&CoopMessage::Restart);
+            }
+            coop.dealing = false;
+        }
+    }
+
+    if let Some(board) = &coop.board {
+        board.apply(&mut settings);
+    }
+    flag_owners.0.clear();
+}
+
+/// One mine ends the game for everyone, so there are no spare lives.
+pub fn apply_coop_rules(net: Res<CoopNet>, mut lives: ResMut<Lives>) {
+    if !matches!(*net, CoopNet::Off) {
+        lives.remaining = 0;
+    }
+}
+
+/// A player's moves only take effect once the host has played them, so they are taken out of
+/// the events before this board sees them.
+pub fn forward_coop_moves(
+    net: Res<CoopNet>,
+    mut tile_revealed_events: ResMut<Events<TileRevealedEvent>>,
+    mut tile_flagged_events: ResMut<Events<TileFlaggedEvent>>,
+    mut chord_events: ResMut<Events<ChordEvent>>,
+) {
+    let CoopNet::Client(client) = &*net else {
+        return;
+    };
+
+    let send = |action: Action| client.send(&CoopMessage::Move { action });
+    for Position { x, y } in tile_revealed_events.drain().map(|event| event.position) {
+        send(Action::Reveal { x, y });
+    }
+    for Position { x, y } in tile_flagged_events.drain().map(|event| event.position) {
+        send(Action::Flag { x, y });
+    }
+    for Position { x, y } in chord_events.drain().map(|event| event.position) {
+        send(Action::Chord { x, y });
+    }
+}
+
+pub fn apply_coop_tiles(
+    mut coop: ResMut<Coop>,
+    settings: Res<BoardSettings>,
+    mut flag_owners: ResMut<FlagOwners>,
+    mut game_stats: ResMut<GameStats>,
+    mut tile_query: Query<(&Position, &mut Tile), With<TileSprite>>,
+) {
+    if coop.pending.is_empty() {
+        return;
+    }
+
+    // Later states of a tile replace earlier ones.
+    let states: HashMap<Position, TileState> = coop
+        .pending
+        .drain(..)
+        .map(|state| (state.position(), state))
+        .collect();
+    for (position, mut tile) in tile_query.iter_mut() {
+        let Some(state) = states.get(position) else {
+            continue;
+        };
+        state.apply(&mut tile);
+        match state.owner {
+            Some(owner) if state.flags > 0 => flag_owners.0.insert(*position, owner),
+            _ => flag_owners.0.remove(position),
+        };
+    }
+
+    let flags: i16 = tile_query.iter().map(|(_, tile)| tile.flags as i16).sum();
+    game_stats.mines_remaining = settings.mine_count as i16 - flags;
+}
+
+/// Sends everyone the tiles the host's board changed this frame, crediting new flags, and the
+/// shared loss when a mine goes off.
+pub fn broadcast_coop_tiles(
+    net: Res<CoopNet>,
+    mut coop: ResMut<Coop>,
+    mut flag_owners: ResMut<FlagOwners>,
+    tile_query: Query<(&Position, Ref<Tile>), With<TileSprite>>,
+    mut game_won_events: EventReader<GameWonEvent>,
+    mut game_over_events: EventReader<GameOverEvent>,
+) {
+    let CoopNet::Host(host) = &*net else {
+        return;
+    };
+    // Winning flags every mine, and nobody placed those.
+    let won = game_won_events.read().count() > 0;
+
+    let mut tiles = Vec::new();
+    for (position, tile) in tile_query.iter() {
+        if !tile.is_changed() || tile.is_added() {
+            continue;
+        }
+        let owner = match flag_owners.0.get(position) {
+            Some(owner) if tile.is_flagged() => Some(*owner),
+            None if tile.is_flagged() && !won => {
+                let owner = coop
+                    .peer_flags
+                    .iter()
+                    .rev()
+                    .find(|(flagged, _)| flagged == position)
+                    .map_or(HOST, |(_, player)| *player);
+                flag_owners.0.insert(*position, owner);
+                Some(owner)
+            }
+            Some(_) => {
+                flag_owners.0.remove(position);
+                None
+            }
+            None => None,
+        };
+        tiles.push(TileState::new(*position, &tile, owner));
+    }
+    if !coop.peer_flags.is_empty() {
+        coop.peer_flags.clear();
+    }
+
+    if !tiles.is_empty() {
+        host.broadcast(&CoopMessage::Tiles { tiles });
+    }
+    if game_over_events.read().count() > 0 {
+        host.broadcast(&CoopMessage::Lost);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn send_coop_cursor(
+    time: Res<Time>,
+    net: Res<CoopNet>,
+    coop: Res<Coop>,
+    settings: Res<BoardSettings>,
+    windows: Query<&Window>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    mut sent: Local<Option<Vec2>>,
+    mut cooldown: Local<f32>,
+) {
+    let Some(me) = coop.me else {
+        return;
+    };
+    *cooldown -= time.delta_secs();
+    if *cooldown > 0.0 {
+        return;
+    }
+
+    let (camera, camera_transform) = *camera;
+    let Some(point) = windows
+        .single()
+        .ok()
+        .and_then(Window::cursor_position)
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+        .map(|world_pos| world_to_board(world_pos, &settings))
+    else {
+        return;
+    };
+    if *sent == Some(point) {
+        return;
+    }
+    *sent = Some(point);
+    *cooldown = CURSOR_SEND_INTERVAL;
+
+    let message = CoopMessage::Cursor {
+        player: me,
+        x: point.x,
+        y: point.y,
+    };
+    match &*net {
+        CoopNet::Off => {}
+        CoopNet::Host(host) => host.broadcast(&message),
+        CoopNet::Client(client) => client.send(&message),
+    }
+}
+
+pub fn show_coop_cursors(
+    mut commands: Commands,
+    coop: Res<Coop>,
+    settings: Res<BoardSettings>,
+    mut cursor_query: Query<(Entity, &CoopCursor, &mut Transform)>,
+) {
+    for (entity, cursor, mut transform) in cursor_query.iter_mut() {
+        match coop.cursors.get(&cursor.player) {
+            Some(point) => {
+                transform.translation = board_to_world(*point, &settings).extend(CURSOR_Z);
+            }
+            None => commands.entity(entity).despawn(),
+        }
+    }
+
+    for (player, point) in &coop.cursors {
+        if cursor_query
+            .iter()
+            .any(|(_, cursor, _)| cursor.player == *player)
+        {
+            continue;
+        }
+        let color = player_color(*player);
+        commands
+            .spawn((
+                Sprite::from_color(color, Vec2::splat(CURSOR_SIZE)),
+                Transform::from_translation(board_to_world(*point, &settings).extend(CURSOR_Z)),
+                CoopCursor { player: *player },
+            ))
+            .with_children(|builder| {
+                builder.spawn((
+                    Text2d::new(coop.name(*player)),
+                    TextFont {
+                        font_size: CURSOR_LABEL_SIZE,
+                        ..default()
+                    },
+                    TextColor(color),
+                    Transform::from_xyz(0.0, -CURSOR_SIZE - 4.0, 0.0),
+                ));
+            });
+    }
+}
+
+/// Marks each flag with its owner's colour, on the wide side of triangle cells.
+pub fn show_flag_owners(
+    mut commands: Commands,
+    settings: Res<BoardSettings>,
+    flag_owners: Res<FlagOwners>,
+    mark_query: Query<Entity, With<FlagOwnerMark>>,
+) {
+    for entity in mark_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let tile_size = tile_sprite_size(&settings);
+    for (position, owner) in &flag_owners.0 {
+        let offset = if tile_flipped(*position, &settings) {
+            0.22
+        } else {
+            -0.22
+        };
+        let translation = tile_translation(*position, &settings) + Vec2::Y * tile_size.y * offset;
+        commands.spawn((
+            Sprite::from_color(
+                player_color(*owner),
+                Vec2::splat(tile_size.x.min(tile_size.y) * FLAG_MARK_SCALE),
+            ),
+            Transform::from_translation(translation.extend(FLAG_MARK_Z)),
+            FlagOwnerMark,
+        ));
+    }
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct EditorCell;
+
+#[derive(Component)]
+pub struct EditorStatusText;
@@ -0,0 +1,42 @@
+pub mod components;
+pub mod resources;
+pub mod systems;
+
+use crate::{
+    board::BoardSettings,
+    game::{GameState, events::NewGameEvent},
+    mode::systems::start_run,
+};
+use bevy::prelude::*;
+pub use components::*;
+pub use resources::*;
+use systems::*;
+
+pub struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Editor>()
+            .add_systems(OnEnter(GameState::Editing), enter_editor)
+            .add_systems(OnExit(GameState::Editing), despawn_editor)
+            .add_systems(
+                Update,
+                open_editor.run_if(not(in_state(GameState::Editing))),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_editor_keys,
+                    paint_editor_cells,
+                    sync_editor_board.run_if(resource_changed::<Editor>),
+                    spawn_editor_cells
+                        .run_if(resource_changed::<Editor>.or(resource_changed::<BoardSettings>)),
+                    update_editor_status.run_if(resource_changed::<Editor>),
+                    leave_editor.run_if(on_event::<NewGameEvent>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Editing))
+                    .before(start_run),
+            );
+    }
+}
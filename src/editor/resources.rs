@@ -0,0 +1,163 @@
+use crate::{
+    board::{BoardSettings, layout::BoardLayout},
+    config::{BOARD_HEIGHT, BOARD_WIDTH},
+    puzzle::{Level, LevelPack},
+    storage::{load_json, save_json},
+};
+use bevy::prelude::*;
+
+/// The most mines the editor lets a single cell hold.
+pub const MAX_MINES_PER_CELL: u8 = 3;
+pub const MIN_SIZE: u8 = 2;
+pub const MAX_SIZE: u8 = 64;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum EditorBrush {
+    #[default]
+    Mine,
+    Hole,
+    Open,
+}
+
+impl EditorBrush {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EditorBrush::Mine => "Mine",
+            EditorBrush::Hole => "Hole",
+            EditorBrush::Open => "Open",
+        }
+    }
+}
+
+/// The board being edited, kept between visits to the editor.
+#[derive(Resource)]
+pub struct Editor {
+    pub layout: BoardLayout,
+    pub brush: EditorBrush,
+    pub status: String,
+    /// Index of the saved board being edited, so saving overwrites it.
+    pub loaded: Option<usize>,
+    /// The settings to put back when leaving without playing the board.
+    pub previous: Option<BoardSettings>,
+    pub play: bool,
+}
+
+impl Default for Editor {
+    fn default() -> Self {
+        Self {
+            layout: BoardLayout::empty(BOARD_WIDTH, BOARD_HEIGHT),
+            brush: EditorBrush::default(),
+            status: String::new(),
+            loaded: None,
+            previous: None,
+            play: false,
+        }
+    }
+}
+
+impl Editor {
+    pub fn paint(&mut self, index: usize) {
+        let layout = &mut self.layout;
+        let mut cells: Vec<bool> = (0..layout.mines.len())
+            .map(|cell| layout.has_cell(cell))
+            .collect();
+
+        match self.brush {
+            EditorBrush::Mine => {
+                let mines = (layout.mines[index] + 1) % (MAX_MINES_PER_CELL + 1);
+                if mines > 0 && layout.mine_count() == u8::MAX {
+                    self.status = "A board holds at most 255 mines".to_string();
+                    return;
+                }
+                layout.mines[index] = mines;
+                layout.revealed[index] = false;
+                cells[index] = true;
+            }
+            EditorBrush::Hole => {
+                cells[index] = !cells[index];
+                layout.mines[index] = 0;
+                layout.revealed[index] = false;
+            }
+            EditorBrush::Open => {
+                layout.revealed[index] = !layout.revealed[index] || layout.mines[index] > 0;
+                layout.mines[index] = 0;
+                cells[index] = true;
+            }
+        }
+        layout.flags[index] = 0;
+        layout.set_cells(cells);
+    }
+
+    /// Turns a cell back into a plain hidden safe cell.
+    pub fn erase(&mut self, index: usize) {
+        let layout = &mut self.layout;
+        let mut cells: Vec<bool> = (0..layout.mines.len())
+            .map(|cell| layout.has_cell(cell))
+            .collect();
+
+        cells[index] = true;
+        layout.mines[index] = 0;
+        layout.revealed[index] = false;
+        layout.flags[index] = 0;
+        layout.set_cells(cells);
+    }
+
+    pub fn resize(&mut self, width: u8, height: u8) {
+        let width = width.clamp(MIN_SIZE, MAX_SIZE);
+        let height = height.clamp(MIN_SIZE, MAX_SIZE);
+        self.layout = self.layout.resized(width, height);
+    }
+}
+
+/// Boards saved from the editor, stored as a level pack so they also show up in puzzle mode.
+pub struct SavedBoards;
+
+impl SavedBoards {
+    const FILE_NAME: &'static str = "levels/editor.json";
+    const PACK_NAME: &'static str = "My Boards";
+
+    pub fn load() -> LevelPack {
+        load_json::<Option<LevelPack>>(Self::FILE_NAME).unwrap_or_else(|| LevelPack {
+            name: Self::PACK_NAME.to_string(),
+            levels: Vec::new(),
+        })
+    }
+
+    /// Saves `layout` over the board at `index`, or as a new one; returns where it went.
+    pub fn save(layout: &BoardLayout, index: Option<usize>) -> Result<(usize, String), String> {
+        let rows = layout.to_rows();
+        BoardLayout::from_rows("", &rows).map_err(|err| err.to_string())?;
+
+        let mut pack = Self::load();
+        let safe_cells = (0..layout.mines.len())
+            .filter(|cell| layout.has_cell(*cell) && layout.mines[*cell] == 0)
+            .count();
+        // One second per safe cell is a loose par until someone beats it.
+        let par_seconds = safe_cells as u32;
+
+        let index = match index.filter(|index| *index < pack.levels.len()) {
+            Some(index) => {
+                pack.levels[index].rows = rows;
+                pack.levels[index].par_seconds = par_seconds;
+                index
+            }
+            None => {
+                let number = (1..)
+                    .find(|number| {
+                        let title = format!("Board {number}");
+                        !pack.levels.iter().any(|level| level.title == title)
+                    })
+                    .unwrap_or_default();
+                pack.levels.push(Level {
+                    title: format!("Board {number}"),
+                    par_seconds,
+                    rows,
+                });
+                pack.levels.len() - 1
+            }
+        };
+
+        save_json(Self::FILE_NAME, &pack).map_err(|err| err.to_string())?;
+        Ok((index, pack.levels[index].title.clone()))
+    }
+}
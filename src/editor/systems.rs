@@ -0,0 +1,327 @@
+use crate::{
+    board::{
+        BoardSettings,
+        generation::count_adjacent_mines,
+        grid::{neighbour_table, tile_position},
+        layout::BoardLayout,
+        systems::{BoardBackground, mine_count_text_size},
+    },
+    editor::*,
+    game::{GameState, components::OverlayText, events::NewGameEvent},
+    mode::GameMode,
+    puzzle::PuzzleLibrary,
+    solver::solve_layout,
+    tile::{Position, TileSprite, TileText, colors::number_color},
+    utils::{contains_point, tile_flipped, tile_sprite_size, tile_translation},
+};
+use bevy::{
+    color::palettes::css::{BLACK, LIGHT_GRAY, MAROON, WHITE},
+    prelude::*,
+};
+
+const STATUS_FONT_SIZE: f32 = 14.0;
+
+pub fn open_editor(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyE) {
+        next_state.set(GameState::Editing);
+    }
+}
+
+/// Swaps the game board for the editor's, starting from the current board if it is a fixed one.
+#[allow(clippy::type_complexity)]
+pub fn enter_editor(
+    mut commands: Commands,
+    mut editor: ResMut<Editor>,
+    mut settings: ResMut<BoardSettings>,
+    board_entities: Query<
+        Entity,
+        Or<(
+            With<TileSprite>,
+            With<TileText>,
+            With<BoardBackground>,
+            With<OverlayText>,
+        )>,
+    >,
+) {
+    for entity in board_entities.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    if let Some(layout) = &settings.layout {
+        editor.layout = layout.clone();
+        editor.loaded = None;
+    }
+    editor.previous = Some(settings.clone());
+    editor.status.clear();
+    settings.apply_layout(editor.layout.clone());
+
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font_size: STATUS_FONT_SIZE,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(6.0),
+            left: Val::Px(8.0),
+            padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0., 0., 0., 0.6)),
+        EditorStatusText,
+    ));
+}
+
+pub fn handle_editor_keys(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    settings: Res<BoardSettings>,
+    mut editor: ResMut<Editor>,
+    mut game_mode: ResMut<GameMode>,
+    mut library: ResMut<PuzzleLibrary>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+) {
+    let (width, height) = (editor.layout.width, editor.layout.height);
+
+    for key in keyboard_input.get_just_pressed() {
+        match key {
+            KeyCode::Digit1 => editor.brush = EditorBrush::Mine,
+            KeyCode::Digit2 => editor.brush = EditorBrush::Hole,
+            KeyCode::Digit3 => editor.brush = EditorBrush::Open,
+            KeyCode::BracketLeft => editor.resize(width.saturating_sub(1), height),
+            KeyCode::BracketRight => editor.resize(width.saturating_add(1), height),
+            KeyCode::Minus => editor.resize(width, height.saturating_sub(1)),
+            KeyCode::Equal => editor.resize(width, height.saturating_add(1)),
+            KeyCode::KeyN => {
+                editor.layout = BoardLayout::empty(width, height);
+                editor.loaded = None;
+                editor.status = "Cleared".to_string();
+            }
+            KeyCode::KeyC => editor.status = check_board(&editor, &settings),
+            KeyCode::KeyS => match SavedBoards::save(&editor.layout, editor.loaded) {
+                Ok((index, title)) => {
+                    editor.loaded = Some(index);
+                    editor.status = format!("Saved as {title}");
+                    *library = PuzzleLibrary::load();
+                }
+                Err(err) => editor.status = format!("Could not save: {err}"),
+            },
+            KeyCode::KeyO => {
+                let pack = SavedBoards::load();
+                let index = editor
+                    .loaded
+                    .map_or(0, |index| (index + 1) % pack.levels.len().max(1));
+                match pack.levels.get(index).map(|level| (level, level.layout())) {
+                    Some((level, Ok(layout))) => {
+                        editor.layout = layout;
+                        editor.loaded = Some(index);
+                        editor.status = format!("Loaded {}", level.title);
+                    }
+                    Some((level, Err(err))) => {
+                        editor.status = format!("Could not load {}: {err}", level.title);
+                    }
+                    None => editor.status = "No saved boards yet".to_string(),
+                }
+            }
+            KeyCode::Enter => {
+                editor.play = true;
+                game_mode.set_if_neq(GameMode::Classic);
+                new_game_events.write(NewGameEvent);
+            }
+            KeyCode::KeyE => {
+                new_game_events.write(NewGameEvent);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_board(editor: &Editor, settings: &BoardSettings) -> String {
+    let mut board = settings.clone();
+    board.apply_layout(editor.layout.clone());
+    let report = solve_layout(
+        &editor.layout,
+        &neighbour_table(&board),
+        board.max_mines_per_cell(),
+    );
+
+    if report.solved {
+        "Solvable without guessing".to_string()
+    } else {
+        format!(
+            "Needs a guess: logic opens {} of {} safe cells",
+            report.revealed_cells, report.safe_cells
+        )
+    }
+}
+
+pub fn paint_editor_cells(
+    windows: Query<&Window>,
+    settings: Res<BoardSettings>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    mut editor: ResMut<Editor>,
+    ui_interactions: Query<&Interaction>,
+) {
+    let left_click = mouse_input.just_pressed(MouseButton::Left);
+    let right_click = mouse_input.just_pressed(MouseButton::Right);
+
+    if !left_click && !right_click {
+        return;
+    }
+
+    if ui_interactions
+        .iter()
+        .any(|interaction| *interaction != Interaction::None)
+    {
+        return;
+    }
+
+    let (camera, camera_transform) = *camera;
+    let Ok(window) = windows.single() else {
+        return;
+    };
+
+    if let Some(cursor_position) = window.cursor_position()
+        && let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_position)
+        && let Some(index) = (0..editor.layout.mines.len())
+            .find(|index| contains_point(tile_position(*index, &settings), world_pos, &settings))
+    {
+        if left_click {
+            editor.paint(index);
+        } else {
+            editor.erase(index);
+        }
+    }
+}
+
+pub fn sync_editor_board(editor: Res<Editor>, mut settings: ResMut<BoardSettings>) {
+    settings.apply_layout(editor.layout.clone());
+}
+
+/// Redraws every cell, showing mines and holes along with the numbers they produce.
+pub fn spawn_editor_cells(
+    mut commands: Commands,
+    editor: Res<Editor>,
+    settings: Res<BoardSettings>,
+    asset_server: Res<AssetServer>,
+    cell_entities: Query<Entity, With<EditorCell>>,
+) {
+    for entity in cell_entities.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let layout = &editor.layout;
+    for index in 0..layout.mines.len() {
+        let position = tile_position(index, &settings);
+        let (color, label) = cell_appearance(index, position, &editor, &settings);
+        let translation = tile_translation(position, &settings);
+
+        commands
+            .spawn((
+                Sprite {
+                    color,
+                    custom_size: Some(tile_sprite_size(&settings)),
+                    flip_y: tile_flipped(position, &settings),
+                    ..default()
+                },
+                Transform::from_translation(translation.extend(0.0)),
+                position,
+                EditorCell,
+            ))
+            .with_children(|cell| {
+                if let Some((text, text_color)) = label {
+                    cell.spawn((
+                        Text2d::new(text),
+                        TextColor(text_color),
+                        TextFont {
+                            font: asset_server.load("mine-sweeper.otf"),
+                            font_size: mine_count_text_size(settings.tile_size),
+                            ..default()
+                        },
+                        TextLayout::new_with_justify(JustifyText::Center),
+                        Transform::from_translation(Vec3::Z),
+                    ));
+                }
+            });
+    }
+}
+
+fn cell_appearance(
+    index: usize,
+    position: Position,
+    editor: &Editor,
+    settings: &BoardSettings,
+) -> (Color, Option<(String, Color)>) {
+    let layout = &editor.layout;
+
+    if !layout.has_cell(index) {
+        return (Color::srgba(1., 1., 1., 0.08), None);
+    }
+
+    match layout.mines[index] {
+        0 => {
+            let adjacent_mines = count_adjacent_mines(&layout.mines, position, settings);
+            let color = if layout.revealed[index] {
+                BLACK
+            } else {
+                LIGHT_GRAY
+            };
+            let label = (adjacent_mines > 0).then(|| {
+                (
+                    adjacent_mines.to_string(),
+                    number_color(adjacent_mines).into(),
+                )
+            });
+            (color.into(), label)
+        }
+        1 => (MAROON.into(), Some(("*".to_string(), WHITE.into()))),
+        mines => (MAROON.into(), Some((mines.to_string(), WHITE.into()))),
+    }
+}
+
+pub fn update_editor_status(
+    editor: Res<Editor>,
+    mut status: Single<&mut Text, With<EditorStatusText>>,
+) {
+    let layout = &editor.layout;
+    status.0 = format!(
+        "Editor {}x{}  Mines: {}  Brush: {}\n\
+         1 Mine  2 Hole  3 Open  Right click: erase\n\
+         [ ] width  - = height  N clear\n\
+         C check  S save  O load  X export  B import  Enter play  E exit\n{}",
+        layout.width,
+        layout.height,
+        layout.mine_count(),
+        editor.brush.name(),
+        editor.status
+    );
+}
+
+/// Puts back the board the editor replaced, or plays the edited one.
+pub fn leave_editor(mut editor: ResMut<Editor>, mut settings: ResMut<BoardSettings>) {
+    let previous = editor.previous.take();
+
+    if editor.play {
+        editor.play = false;
+        settings.apply_layout(editor.layout.clone());
+    } else if let Some(previous) = previous {
+        *settings = BoardSettings {
+            tile_size: settings.tile_size,
+            ..previous
+        };
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn despawn_editor(
+    mut commands: Commands,
+    editor_entities: Query<Entity, Or<(With<EditorCell>, With<EditorStatusText>)>>,
+) {
+    for entity in editor_entities.iter() {
+        commands.entity(entity).despawn();
+    }
+}
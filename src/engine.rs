@@ -0,0 +1,199 @@
+use crate::{
+    board::{
+        BoardSettings,
+        generation::{deal_board, three_bv},
+        grid::neighbour_table,
+        layout::BoardLayout,
+    },
+    game::ClickKind,
+    rules::{Minefield, Reveal},
+    solver::SolverBoard,
+    tile::Tile,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Playing,
+    Won,
+    Lost,
+}
+
+/// A game on plain vectors, following the same [`rules`](crate::rules) as the board plugin,
+/// for frontends and tools that run without a window. Cells are tile indices, and a mine ends
+/// the game unless the minefield is given lives or claims mines.
+#[derive(Clone, Debug)]
+pub struct Engine {
+    pub settings: BoardSettings,
+    /// The mines, and what has been opened and flagged so far.
+    pub board: Minefield,
+    pub outcome: Outcome,
+    /// The mine that ended the game.
+    pub exploded: Option<usize>,
+    three_bv: u16,
+}
+
+impl Engine {
+    /// Deals a board the way the game would for `settings`.
+    pub fn new(settings: &BoardSettings) -> Self {
+        let layout = deal_board(settings);
+        let mut engine = Self {
+            three_bv: three_bv(&layout.mines, &layout.revealed, settings),
+            settings: settings.clone(),
+            board: Minefield::from_layout(
+                &layout,
+                neighbour_table(settings),
+                settings.max_mines_per_cell(),
+            ),
+            outcome: Outcome::Playing,
+            exploded: None,
+        };
+        engine.check_won();
+        engine
+    }
+
+    pub fn three_bv(&self) -> u16 {
+        self.three_bv
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.board.tiles.len()
+    }
+
+    pub fn has_cell(&self, cell: usize) -> bool {
+        self.board.tile(cell).is_some()
+    }
+
+    pub fn is_mine(&self, cell: usize) -> bool {
+        self.board.tile(cell).is_some_and(Tile::is_mine)
+    }
+
+    pub fn flags(&self, cell: usize) -> u8 {
+        self.board.tile(cell).map_or(0, |tile| tile.flags)
+    }
+
+    /// The number a safe cell shows once opened.
+    pub fn number(&self, cell: usize) -> u8 {
+        self.board.tile(cell).map_or(0, |tile| tile.adjacent_mines)
+    }
+
+    pub fn is_hidden(&self, cell: usize) -> bool {
+        self.board.is_hidden(cell)
+    }
+
+    pub fn is_revealed(&self, cell: usize) -> bool {
+        self.board.tile(cell).is_some_and(|tile| tile.is_revealed)
+    }
+
+    /// The mines, and what has been opened and flagged so far, as a fixed board.
+    pub fn layout(&self) -> BoardLayout {
+        let mut layout = BoardLayout {
+            mask: self.settings.mask.clone(),
+            ..BoardLayout::empty(self.settings.width, self.settings.height)
+        };
+        for (cell, tile) in self.board.tiles.iter().enumerate() {
+            if let Some(tile) = tile {
+                layout.mines[cell] = tile.mines;
+                layout.revealed[cell] = tile.is_revealed;
+                layout.flags[cell] = tile.flags;
+            }
+        }
+        layout
+    }
+
+    /// The hidden blank cell nearest the middle, where a player who knows the board would start;
+    /// any hidden safe cell if there are no blanks.
+    pub fn opening_cell(&self) -> Option<usize> {
+        let width = self.settings.width as usize;
+        let centre = (
+            self.settings.width as f32 / 2.0,
+            self.settings.height as f32 / 2.0,
+        );
+        let distance = |cell: usize| {
+            let (x, y) = ((cell % width) as f32 + 0.5, (cell / width) as f32 + 0.5);
+            (x - centre.0).powi(2) + (y - centre.1).powi(2)
+        };
+
+        (0..self.cell_count())
+            .filter(|cell| self.is_hidden(*cell) && !self.is_mine(*cell))
+            .min_by(|a, b| {
+                (self.number(*a) > 0)
+                    .cmp(&(self.number(*b) > 0))
+                    .then(distance(*a).total_cmp(&distance(*b)))
+            })
+    }
+
+    /// Opens `cell`, spreading from blanks; returns whether anything changed.
+    pub fn reveal(&mut self, cell: usize) -> bool {
+        if self.outcome != Outcome::Playing {
+            return false;
+        }
+
+        match self.board.reveal(cell) {
+            Reveal::Nothing => return false,
+            Reveal::Exploded => {
+                self.outcome = Outcome::Lost;
+                self.exploded = Some(cell);
+                self.board.lose();
+            }
+            Reveal::Safe | Reveal::Claimed => self.check_won(),
+        }
+        true
+    }
+
+    /// Cycles the flags on a hidden cell.
+    pub fn flag(&mut self, cell: usize) -> bool {
+        self.outcome == Outcome::Playing && self.board.flag(cell)
+    }
+
+    /// Opens the neighbours of an opened number once its flags are all placed.
+    pub fn chord(&mut self, cell: usize) -> bool {
+        let mut changed = false;
+        for neighbour in self.board.chord_targets(cell) {
+            changed |= self.reveal(neighbour);
+        }
+        changed
+    }
+
+    /// A click as the window handles it: left opens a cell or chords an opened number, right
+    /// flags. Returns what kind of click it was and whether it was wasted.
+    pub fn click(&mut self, cell: usize, left_click: bool) -> (ClickKind, bool) {
+        if !left_click {
+            (ClickKind::Right, !self.flag(cell))
+        } else if !self.is_revealed(cell) {
+            (ClickKind::Left, !self.reveal(cell))
+        } else if self.board.can_chord(cell) {
+            (ClickKind::Chord, !self.chord(cell))
+        } else {
+            (ClickKind::Left, true)
+        }
+    }
+
+    /// What a player sees, with flags taken at their word.
+    pub fn visible(&self) -> SolverBoard {
+        self.board.visible()
+    }
+
+    /// Flags every provable mine and opens every provably safe cell; returns whether the
+    /// solver found anything.
+    pub fn apply_deductions(&mut self) -> bool {
+        let deductions = self.visible().deduce();
+        for (cell, mines) in &deductions.mines {
+            if let Some(tile) = self.board.tiles[*cell].as_mut() {
+                tile.flags = *mines;
+            }
+        }
+        for cell in &deductions.safe {
+            self.reveal(*cell);
+        }
+        !deductions.is_empty()
+    }
+
+    fn check_won(&mut self) {
+        if self.outcome == Outcome::Playing && self.board.is_cleared() {
+            self.outcome = Outcome::Won;
+            self.board.win();
+        }
+    }
+}
@@ -16,6 +16,10 @@ pub struct TileFlaggedEvent {
     pub position: Position,
 }
 
+/// A mine was hit without ending the game, thanks to a spare life or rush mode.
+#[derive(Event)]
+pub struct MineHitEvent;
+
 #[derive(Event)]
 pub struct GameOverEvent;
 
@@ -23,4 +27,4 @@ pub struct GameOverEvent;
 pub struct GameWonEvent;
 
 #[derive(Event)]
-pub struct NewGameEvent;
\ No newline at end of file
+pub struct NewGameEvent;
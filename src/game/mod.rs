@@ -1,32 +1,47 @@
+#[cfg(feature = "gui")]
 pub mod components;
+#[cfg(feature = "gui")]
 pub mod events;
 pub mod resources;
+#[cfg(feature = "gui")]
 pub mod state;
+#[cfg(feature = "gui")]
 pub mod systems;
 
+#[cfg(feature = "gui")]
 use bevy::prelude::*;
+#[cfg(feature = "gui")]
 pub use events::*;
 pub use resources::*;
+#[cfg(feature = "gui")]
 pub use state::GameState;
+#[cfg(feature = "gui")]
 use systems::*;
 
+#[cfg(feature = "gui")]
 pub struct GamePlugin;
 
+#[cfg(feature = "gui")]
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<TileRevealedEvent>()
             .add_event::<TileFlaggedEvent>()
             .add_event::<ChordEvent>()
+            .add_event::<MineHitEvent>()
             .add_event::<GameOverEvent>()
             .add_event::<GameWonEvent>()
             .add_event::<NewGameEvent>()
             .init_resource::<GameStats>()
+            .init_resource::<LivesSettings>()
+            .init_resource::<Lives>()
             .add_systems(
                 Update,
                 (
                     reset_game_input,
                     handle_new_game,
+                    layout_overlay_text,
                     (
+                        tick_game_time,
                         handle_tile_revealed,
                         handle_tile_flagged,
                         check_win_condition,
@@ -1,16 +1,184 @@
-use bevy::prelude::*;
+use crate::{board::BoardSettings, tile::Position};
 
-#[derive(Resource, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ClickKind {
+    Left,
+    Right,
+    Chord,
+}
+
+/// A click on the board, kept so the game can be reviewed afterwards.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Click {
+    pub position: Position,
+    pub kind: ClickKind,
+    pub wasted: bool,
+}
+
+#[cfg_attr(feature = "gui", derive(bevy::prelude::Resource))]
+#[derive(Debug)]
 pub struct GameStats {
     pub mines_remaining: i16,
     pub tiles_revealed: u16,
+    /// The fewest clicks that clear the board, see [`crate::board::generation::three_bv`].
+    pub three_bv: u16,
+    pub left_clicks: u16,
+    pub right_clicks: u16,
+    pub chord_clicks: u16,
+    /// Clicks of any kind that changed nothing.
+    pub wasted_clicks: u16,
+    /// Time on this board, counted from the first click.
+    pub seconds: f32,
+    pub click_log: Vec<Click>,
+    /// The solver made moves on this board, so it does not count towards records.
+    pub assisted: bool,
 }
 
-impl Default for GameStats {
-    fn default() -> Self {
+impl GameStats {
+    pub fn new(mine_count: u8) -> Self {
         Self {
-            mines_remaining: crate::config::MINE_COUNT as i16,
+            mines_remaining: mine_count as i16,
             tiles_revealed: 0,
+            three_bv: 0,
+            left_clicks: 0,
+            right_clicks: 0,
+            chord_clicks: 0,
+            wasted_clicks: 0,
+            seconds: 0.0,
+            click_log: Vec::new(),
+            assisted: false,
+        }
+    }
+
+    pub fn record_click(&mut self, position: Position, kind: ClickKind, wasted: bool) {
+        self.click_log.push(Click {
+            position,
+            kind,
+            wasted,
+        });
+        match kind {
+            ClickKind::Left => self.left_clicks += 1,
+            ClickKind::Right => self.right_clicks += 1,
+            ClickKind::Chord => self.chord_clicks += 1,
+        }
+        if wasted {
+            self.wasted_clicks += 1;
+        }
+    }
+
+    pub fn clicks(&self) -> u16 {
+        self.left_clicks + self.right_clicks + self.chord_clicks
+    }
+
+    pub fn three_bv_per_second(&self) -> f32 {
+        if self.seconds > 0.0 {
+            self.three_bv as f32 / self.seconds
+        } else {
+            0.0
+        }
+    }
+
+    /// Index of efficiency: 3BV per click, where 1 means no click was spent beyond the minimum.
+    pub fn ioe(&self) -> f32 {
+        match self.clicks() {
+            0 => 0.0,
+            clicks => self.three_bv as f32 / clicks as f32,
+        }
+    }
+
+    /// Rapidity quality product: time over 3BV/s, lower is better.
+    pub fn rqp(&self) -> f32 {
+        match self.three_bv_per_second() {
+            0.0 => 0.0,
+            speed => self.seconds / speed,
+        }
+    }
+
+    /// Accounts for the flags and open cells a fixed layout starts with.
+    pub fn for_board(settings: &BoardSettings) -> Self {
+        let mut stats = Self::new(settings.mine_count);
+        if let Some(layout) = &settings.layout {
+            stats.mines_remaining -= layout.flags.iter().map(|flags| *flags as i16).sum::<i16>();
+            stats.tiles_revealed =
+                layout.revealed.iter().filter(|revealed| **revealed).count() as u16;
+        }
+        stats
+    }
+}
+
+impl Default for GameStats {
+    fn default() -> Self {
+        Self::new(crate::config::MINE_COUNT)
+    }
+}
+
+/// Lives granted per game; zero keeps the classic rules where the first mine ends the game.
+#[cfg_attr(feature = "gui", derive(bevy::prelude::Resource))]
+#[derive(Debug, Default)]
+pub struct LivesSettings {
+    pub lives: u8,
+}
+
+impl LivesSettings {
+    pub const OPTIONS: [u8; 4] = [0, 2, 3, 5];
+
+    pub fn next(&self) -> u8 {
+        let index = Self::OPTIONS
+            .iter()
+            .position(|lives| *lives == self.lives)
+            .unwrap_or(0);
+        Self::OPTIONS[(index + 1) % Self::OPTIONS.len()]
+    }
+}
+
+#[cfg_attr(feature = "gui", derive(bevy::prelude::Resource))]
+#[derive(Debug, Default)]
+pub struct Lives {
+    pub remaining: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(three_bv: u16, seconds: f32) -> GameStats {
+        GameStats {
+            three_bv,
+            seconds,
+            ..GameStats::new(10)
+        }
+    }
+
+    #[test]
+    fn every_kind_of_click_counts_against_efficiency() {
+        let mut game = stats(12, 20.0);
+        let position = Position { x: 0, y: 0 };
+        for _ in 0..10 {
+            game.record_click(position, ClickKind::Left, false);
         }
+        game.record_click(position, ClickKind::Right, false);
+        game.record_click(position, ClickKind::Chord, true);
+        game.record_click(position, ClickKind::Right, true);
+        game.record_click(position, ClickKind::Left, false);
+
+        assert_eq!(game.clicks(), 14);
+        assert_eq!(game.wasted_clicks, 2);
+        assert_eq!(game.click_log.len(), 14);
+        assert!((game.ioe() - 12.0 / 14.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn rqp_is_time_over_speed() {
+        let game = stats(10, 20.0);
+        assert!((game.three_bv_per_second() - 0.5).abs() < 1e-6);
+        assert!((game.rqp() - 40.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn a_board_not_started_scores_zero() {
+        let game = stats(10, 0.0);
+        assert_eq!(game.ioe(), 0.0);
+        assert_eq!(game.three_bv_per_second(), 0.0);
+        assert_eq!(game.rqp(), 0.0);
     }
 }
@@ -6,4 +6,5 @@ pub enum GameState {
     Playing,
     Won,
     GameOver,
-}
\ No newline at end of file
+    Editing,
+}
@@ -1,194 +1,216 @@
 use crate::{
-    board::BoardSettings,
+    board::{
+        BoardSettings,
+        grid::{neighbour_table, tile_index, tile_position},
+    },
     game::{components::OverlayText, events::*, resources::*, state::GameState},
+    mode::{GameMode, ModeRun},
+    rules::{self, Minefield, Reveal},
     tile::{Position, Tile},
 };
-use bevy::{prelude::*, text::TextBounds};
+use bevy::{
+    prelude::*,
+    text::{TextBounds, TextLayoutInfo},
+};
 
+pub fn tick_game_time(time: Res<Time>, mut game_stats: ResMut<GameStats>) {
+    if game_stats.clicks() > 0 {
+        game_stats.seconds += time.delta_secs();
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn handle_tile_revealed(
     settings: Res<BoardSettings>,
     mut game_stats: ResMut<GameStats>,
+    mut lives: ResMut<Lives>,
+    run: Res<ModeRun>,
     mut tile_query: Query<(&Position, &mut Tile)>,
+    mut mine_hit_events: EventWriter<MineHitEvent>,
     mut game_over_events: EventWriter<GameOverEvent>,
     mut tile_revealed_events: EventReader<TileRevealedEvent>,
 ) {
-    for event in tile_revealed_events.read() {
-        if let Some((_, mut tile)) = tile_query
-            .iter_mut()
-            .find(|(pos, _)| **pos == event.position)
-        {
-            if tile.is_revealed || tile.is_flagged {
-                continue;
-            }
-
-            tile.reveal();
-            game_stats.tiles_revealed += 1;
+    if tile_revealed_events.is_empty() {
+        return;
+    }
 
-            if tile.is_mine {
-                game_over_events.write(GameOverEvent);
-                return;
-            }
+    // Rush and versus claim the mines they find, and spare lives cover them.
+    let mut field = Minefield {
+        claims_mines: run.mode.claims_mines(),
+        lives: lives.remaining,
+        ..minefield(&settings, tile_query.iter())
+    };
+    let mines_left = field.mines_left();
+    for event in tile_revealed_events.read() {
+        let revealed = field.reveal(tile_index(event.position, &settings));
+        if revealed == Reveal::Nothing {
+            continue;
+        }
 
-            if tile.adjacent_mines == 0 {
-                reveal_adjacent_tiles(event.position, &mut tile_query, &settings);
-            }
+        game_stats.tiles_revealed += 1;
+        if revealed == Reveal::Claimed {
+            mine_hit_events.write(MineHitEvent);
+        } else if revealed == Reveal::Exploded {
+            game_over_events.write(GameOverEvent);
+            break;
         }
     }
+
+    game_stats.mines_remaining += field.mines_left() - mines_left;
+    if lives.remaining != field.lives {
+        lives.remaining = field.lives;
+    }
+    store_minefield(&field, &settings, &mut tile_query);
 }
+
 pub fn handle_chord_tile(
     settings: Res<BoardSettings>,
     mut chord_events: EventReader<ChordEvent>,
-    mut tile_query: Query<(&Position, &mut Tile)>,
+    tile_query: Query<(&Position, &Tile)>,
     mut tile_revealed_events: EventWriter<TileRevealedEvent>,
 ) {
     for event in chord_events.read() {
-        for dy in -1..=1i8 {
-            for dx in -1..=1i8 {
-                if dx == 0 && dy == 0 {
-                    continue;
-                }
-
-                let new_x = event.position.x as i8 + dx;
-                let new_y = event.position.y as i8 + dy;
-
-                if new_x >= 0
-                    && new_y >= 0
-                    && new_x < settings.width as i8
-                    && new_y < settings.height as i8
-                {
-                    let adjacent_pos = Position {
-                        x: new_x as u8,
-                        y: new_y as u8,
-                    };
-
-                    if let Some((_, mut adjacent_tile)) =
-                        tile_query.iter_mut().find(|(pos, _)| **pos == adjacent_pos)
-                    {
-                        tile_revealed_events.write(TileRevealedEvent {
-                            position: adjacent_pos,
-                        });
-                    }
-                }
-            }
-        }
-    }
-}
-
-fn reveal_adjacent_tiles(
-    position: Position,
-    tile_query: &mut Query<(&Position, &mut Tile)>,
-    settings: &BoardSettings,
-) {
-    for dy in -1..=1i8 {
-        for dx in -1..=1i8 {
-            if dx == 0 && dy == 0 {
-                continue;
-            }
-
-            let new_x = position.x as i8 + dx;
-            let new_y = position.y as i8 + dy;
-
-            if new_x >= 0
-                && new_y >= 0
-                && new_x < settings.width as i8
-                && new_y < settings.height as i8
-            {
-                let adjacent_pos = Position {
-                    x: new_x as u8,
-                    y: new_y as u8,
-                };
-
-                if let Some((_, mut adjacent_tile)) =
-                    tile_query.iter_mut().find(|(pos, _)| **pos == adjacent_pos)
-                {
-                    if !adjacent_tile.is_revealed
-                        && !adjacent_tile.is_flagged
-                        && !adjacent_tile.is_mine
-                    {
-                        adjacent_tile.reveal();
-
-                        if adjacent_tile.adjacent_mines == 0 {
-                            reveal_adjacent_tiles(adjacent_pos, tile_query, settings);
-                        }
-                    }
-                }
-            }
+        let field = minefield(&settings, tile_query.iter());
+        for cell in field.chord_targets(tile_index(event.position, &settings)) {
+            tile_revealed_events.write(TileRevealedEvent {
+                position: tile_position(cell, &settings),
+            });
         }
     }
 }
 
 pub fn handle_tile_flagged(
+    settings: Res<BoardSettings>,
     mut tile_flagged_events: EventReader<TileFlaggedEvent>,
     mut tile_query: Query<(&Position, &mut Tile)>,
     mut game_stats: ResMut<GameStats>,
 ) {
-    for event in tile_flagged_events.read() {
-        if let Some((_, mut tile)) = tile_query
-            .iter_mut()
-            .find(|(pos, _)| **pos == event.position)
-        {
-            if tile.is_revealed {
-                continue;
-            }
-
-            tile.toggle_flag();
+    if tile_flagged_events.is_empty() {
+        return;
+    }
 
-            if tile.is_flagged {
-                game_stats.mines_remaining -= 1;
-            } else {
-                game_stats.mines_remaining += 1;
-            }
-        }
+    let mut field = minefield(&settings, tile_query.iter());
+    let mines_left = field.mines_left();
+    for event in tile_flagged_events.read() {
+        field.flag(tile_index(event.position, &settings));
     }
+
+    game_stats.mines_remaining += field.mines_left() - mines_left;
+    store_minefield(&field, &settings, &mut tile_query);
 }
 
 pub fn check_win_condition(
+    run: Res<ModeRun>,
     tile_query: Query<&Tile>,
-    settings: Res<BoardSettings>,
     mut game_won_events: EventWriter<GameWonEvent>,
 ) {
-    let total_tiles = settings.width as usize * settings.height as usize;
-    let revealed_tiles = tile_query.iter().filter(|tile| tile.is_revealed).count() as usize;
-    let mine_count = settings.mine_count as u16;
+    // Versus is won on mines found, see `crate::versus`.
+    if run.mode == GameMode::Versus {
+        return;
+    }
 
-    if revealed_tiles == total_tiles - mine_count as usize {
+    if rules::is_cleared(tile_query.iter()) {
         game_won_events.write(GameWonEvent);
     }
 }
 
+/// The tiles in play as the [`rules`] see them, with the classic rules for mines.
+pub fn minefield<'a>(
+    settings: &BoardSettings,
+    tiles: impl IntoIterator<Item = (&'a Position, &'a Tile)>,
+) -> Minefield {
+    let mut field = Minefield {
+        tiles: vec![None; settings.width as usize * settings.height as usize],
+        neighbours: neighbour_table(settings),
+        max_mines_per_cell: settings.max_mines_per_cell(),
+        claims_mines: false,
+        lives: 0,
+    };
+    // Tiles from a board of another size may linger for a frame after the settings change.
+    for (position, tile) in tiles {
+        if let Some(slot) = field.tiles.get_mut(tile_index(*position, settings)) {
+            *slot = Some(tile.clone());
+        }
+    }
+    field
+}
+
+/// Copies `field` back onto the tiles, touching only those it changed so change detection
+/// sees just them.
+fn store_minefield(
+    field: &Minefield,
+    settings: &BoardSettings,
+    tile_query: &mut Query<(&Position, &mut Tile)>,
+) {
+    for (position, mut tile) in tile_query.iter_mut() {
+        if let Some(stored) = &field.tiles[tile_index(*position, settings)] {
+            tile.set_if_neq(stored.clone());
+        }
+    }
+}
+
 pub fn handle_game_over(
+    settings: Res<BoardSettings>,
     mut game_over_events: EventReader<GameOverEvent>,
     mut next_state: ResMut<NextState<GameState>>,
-    mut tile_query: Query<&mut Tile>,
+    mut tile_query: Query<(&Position, &mut Tile)>,
     mut commands: Commands,
 ) {
     for _ in game_over_events.read() {
         next_state.set(GameState::GameOver);
 
-        for mut tile in tile_query.iter_mut() {
-            if tile.is_mine {
-                tile.reveal();
-            }
-        }
+        let mut field = minefield(&settings, tile_query.iter());
+        field.lose();
+        store_minefield(&field, &settings, &mut tile_query);
 
         show_overlay_text(&mut commands, "Game Over! Press R to restart".to_string());
     }
 }
 
 pub fn handle_game_won(
+    settings: Res<BoardSettings>,
     mut game_won_events: EventReader<GameWonEvent>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut game_stats: ResMut<GameStats>,
+    mut tile_query: Query<(&Position, &mut Tile)>,
     mut commands: Commands,
 ) {
     for _ in game_won_events.read() {
         next_state.set(GameState::Won);
 
-        show_overlay_text(&mut commands, "You Won! Press R to restart".to_string());
+        let mut field = minefield(&settings, tile_query.iter());
+        field.win();
+        store_minefield(&field, &settings, &mut tile_query);
+        game_stats.mines_remaining = 0;
+
+        show_overlay_text(
+            &mut commands,
+            format!(
+                "You Won! Press R to restart\n\
+                 Time {:.2}s  3BV {}  3BV/s {:.2}\n\
+                 IOE {:.2}  RQP {:.1}\n\
+                 Clicks {} (L {} R {} C {}, {} wasted)",
+                game_stats.seconds,
+                game_stats.three_bv,
+                game_stats.three_bv_per_second(),
+                game_stats.ioe(),
+                game_stats.rqp(),
+                game_stats.clicks(),
+                game_stats.left_clicks,
+                game_stats.right_clicks,
+                game_stats.chord_clicks,
+                game_stats.wasted_clicks
+            ),
+        );
     }
 }
 
-fn show_overlay_text(commands: &mut Commands, text: String) {
-    let box_size: Vec2 = Vec2::new(320.0, 25.0);
+const OVERLAY_MAX_WIDTH: f32 = 320.0;
+const OVERLAY_MARGIN: f32 = 10.0;
+const OVERLAY_PADDING: f32 = 7.0;
+
+pub fn show_overlay_text(commands: &mut Commands, text: String) {
+    let box_size: Vec2 = Vec2::new(OVERLAY_MAX_WIDTH, 25.0);
     commands
         .spawn((
             Sprite::from_color(Color::srgba(0., 0., 0., 0.7), box_size),
@@ -206,6 +228,32 @@ fn show_overlay_text(commands: &mut Commands, text: String) {
         });
 }
 
+pub fn layout_overlay_text(
+    window: Single<&Window>,
+    mut overlay_query: Query<(&mut Sprite, &Children), With<OverlayText>>,
+    mut text_query: Query<(&mut TextBounds, &TextLayoutInfo)>,
+) {
+    let box_width = (window.width() - 2.0 * OVERLAY_MARGIN).min(OVERLAY_MAX_WIDTH);
+
+    for (mut sprite, children) in overlay_query.iter_mut() {
+        for child in children.iter() {
+            let Ok((mut bounds, layout_info)) = text_query.get_mut(child) else {
+                continue;
+            };
+
+            if bounds.width != Some(box_width) {
+                *bounds = TextBounds::new_horizontal(box_width);
+            }
+
+            let text_height = layout_info.size.y / window.scale_factor();
+            let box_size = Vec2::new(box_width, text_height + 2.0 * OVERLAY_PADDING);
+            if sprite.custom_size != Some(box_size) {
+                sprite.custom_size = Some(box_size);
+            }
+        }
+    }
+}
+
 fn remove_overlay_screen(
     commands: &mut Commands,
     overlay_entities: Query<Entity, With<OverlayText>>,
@@ -216,14 +264,20 @@ fn remove_overlay_screen(
 }
 
 pub fn handle_new_game(
+    lives_settings: Res<LivesSettings>,
+    run: Res<ModeRun>,
+    mut lives: ResMut<Lives>,
     mut new_game_events: EventReader<NewGameEvent>,
     mut next_state: ResMut<NextState<GameState>>,
-    mut game_stats: ResMut<GameStats>,
     mut commands: Commands,
     overlay_entities: Query<Entity, With<OverlayText>>,
 ) {
     for _ in new_game_events.read() {
-        *game_stats = GameStats::default();
+        lives.remaining = if run.mode.allows_lives() {
+            lives_settings.lives
+        } else {
+            0
+        };
         next_state.set(GameState::Playing);
 
         remove_overlay_screen(&mut commands, overlay_entities);
@@ -0,0 +1,15 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+pub use components::*;
+use systems::*;
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_hud)
+            .add_systems(Update, update_hud);
+    }
+}
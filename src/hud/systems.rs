@@ -0,0 +1,182 @@
+use crate::{
+    coop::Coop,
+    game::{GameStats, Lives, LivesSettings},
+    hud::HudText,
+    mode::{
+        GameMode, ModeRun,
+        daily::{DailyHistory, date_string},
+    },
+    net::HOST,
+    puzzle::{CurrentLevel, PuzzleLibrary},
+    race::Race,
+    versus::Versus,
+};
+use bevy::prelude::*;
+
+const HUD_FONT_SIZE: f32 = 16.0;
+/// Gap between the HUD and the top of the window.
+pub const HUD_TOP: f32 = 6.0;
+
+pub fn spawn_hud(mut commands: Commands) {
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font_size: HUD_FONT_SIZE,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(HUD_TOP),
+            left: Val::Px(8.0),
+            padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0., 0., 0., 0.6)),
+        HudText,
+    ));
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_hud(
+    game_stats: Res<GameStats>,
+    lives: Res<Lives>,
+    lives_settings: Res<LivesSettings>,
+    run: Res<ModeRun>,
+    daily_history: Res<DailyHistory>,
+    puzzle_library: Res<PuzzleLibrary>,
+    current_level: Res<CurrentLevel>,
+    race: Res<Race>,
+    coop: Res<Coop>,
+    versus: Res<Versus>,
+    mut hud: Single<&mut Text, With<HudText>>,
+) {
+    let mut text = format!(
+        "Mines: {}\n3BV: {}  Clicks: {}",
+        game_stats.mines_remaining,
+        game_stats.three_bv,
+        game_stats.clicks()
+    );
+    if lives_settings.lives > 0 && run.mode.allows_lives() {
+        text.push_str(&format!(
+            "\nLives: {}/{}",
+            lives.remaining, lives_settings.lives
+        ));
+    }
+    if run.mode.is_timed() {
+        let seconds = run.clock.ceil() as u32;
+        let score = match run.mode {
+            GameMode::Rush => "Cells",
+            _ => "Boards",
+        };
+        text.push_str(&format!(
+            "\nTime: {}:{:02}\n{score}: {}",
+            seconds / 60,
+            seconds % 60,
+            run.score
+        ));
+    }
+    if run.mode == GameMode::Daily {
+        let attempt = if run.ranked || daily_history.is_ranked(run.day) {
+            "ranked"
+        } else {
+            "practice"
+        };
+        let seconds = run.elapsed as u32;
+        text.push_str(&format!(
+            "\nDaily {} ({attempt})\nTime: {}:{:02}\nStreak: {} (best {})",
+            date_string(run.day),
+            seconds / 60,
+            seconds % 60,
+            daily_history.current_streak(run.day),
+            daily_history.best_streak()
+        ));
+    }
+    if run.mode == GameMode::Puzzle
+        && let Some(level) = puzzle_library.level(*current_level)
+    {
+        let seconds = run.elapsed as u32;
+        text.push_str(&format!(
+            "\nPuzzle: {}\nPar: {}:{:02}\nTime: {}:{:02}\nL: level select",
+            level.title,
+            level.par_seconds / 60,
+            level.par_seconds % 60,
+            seconds / 60,
+            seconds % 60
+        ));
+    }
+    if !race.racers.is_empty() || !race.status.is_empty() {
+        text.push_str(if race.racing() {
+            "\nRace"
+        } else {
+            "\nRace lobby"
+        });
+        if !race.racing() && !race.status.is_empty() {
+            text.push_str(&format!("\n{}", race.status));
+        }
+
+        let mut racers: Vec<_> = race.racers.iter().collect();
+        racers.sort_by_key(|racer| {
+            (
+                racer.place.unwrap_or(u8::MAX),
+                !racer.progress.alive,
+                u8::MAX - racer.progress.cleared,
+            )
+        });
+        for racer in racers {
+            let you = if race.me == Some(racer.id) {
+                " (you)"
+            } else {
+                ""
+            };
+            let standing = match (racer.place, racer.progress.finished) {
+                (Some(place), Some(seconds)) => {
+                    format!("{place}. {}{you}  {seconds:.2}s", racer.name)
+                }
+                _ if !racer.progress.alive => {
+                    format!("-  {}{you}  out at {}%", racer.name, racer.progress.cleared)
+                }
+                _ => format!("   {}{you}  {}%", racer.name, racer.progress.cleared),
+            };
+            text.push_str(&format!("\n{standing}"));
+        }
+        if race.me == Some(HOST) && !race.racing() {
+            text.push_str("\nF5: start a race");
+        }
+    }
+    if !coop.players.is_empty() || !coop.status.is_empty() {
+        text.push_str("\nCo-op");
+        if !coop.status.is_empty() {
+            text.push_str(&format!("\n{}", coop.status));
+        }
+        for player in &coop.players {
+            let you = if coop.me == Some(player.id) {
+                " (you)"
+            } else {
+                ""
+            };
+            text.push_str(&format!("\n   {}{you}", player.name));
+        }
+    }
+    if run.mode == GameMode::Versus {
+        text.push_str(&format!(
+            "\nVersus: first to {} mines",
+            versus.mines / 2 + 1
+        ));
+        if !versus.status.is_empty() {
+            text.push_str(&format!("\n{}", versus.status));
+        }
+        for (seat, name) in versus.names.iter().enumerate() {
+            let turn = if versus.turn == seat { ">" } else { " " };
+            let you = if versus.me == Some(seat) {
+                " (you)"
+            } else {
+                ""
+            };
+            text.push_str(&format!("\n{turn} {name}{you}  {}", versus.scores[seat]));
+        }
+    }
+
+    if hud.0 != text {
+        hud.0 = text;
+    }
+}
@@ -1,6 +1,6 @@
 pub mod systems;
 
-use crate::{game::state::GameState, input::systems::handle_mouse_input};
+use crate::{game::state::GameState, input::systems::handle_mouse_input, replay::replay_running};
 use bevy::prelude::*;
 
 pub struct InputPlugin;
@@ -9,7 +9,9 @@ impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (handle_mouse_input).run_if(in_state(GameState::Playing)),
+            (handle_mouse_input)
+                .run_if(in_state(GameState::Playing))
+                .run_if(not(replay_running)),
         );
     }
 }
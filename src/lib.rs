@@ -0,0 +1,45 @@
+#[cfg(feature = "gui")]
+pub mod accessibility;
+#[cfg(feature = "gui")]
+pub mod analysis;
+#[cfg(feature = "gui")]
+pub mod animation;
+#[cfg(feature = "gui")]
+pub mod autoplay;
+pub mod board;
+pub mod bot;
+pub mod config;
+#[cfg(feature = "gui")]
+pub mod coop;
+#[cfg(feature = "gui")]
+pub mod editor;
+pub mod engine;
+pub mod game;
+#[cfg(feature = "gui")]
+pub mod hud;
+#[cfg(feature = "gui")]
+pub mod input;
+pub mod mode;
+#[cfg(feature = "gui")]
+pub mod net;
+pub mod options;
+#[cfg(feature = "gui")]
+pub mod puzzle;
+#[cfg(feature = "gui")]
+pub mod race;
+#[cfg(feature = "gui")]
+pub mod replay;
+pub mod rules;
+#[cfg(feature = "gui")]
+pub mod settings;
+pub mod solver;
+#[cfg(feature = "gui")]
+pub mod sound;
+pub mod storage;
+pub mod tile;
+#[cfg(feature = "gui")]
+pub mod transfer;
+#[cfg(feature = "gui")]
+pub mod utils;
+#[cfg(feature = "gui")]
+pub mod versus;
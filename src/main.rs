@@ -1,34 +1,82 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-mod board;
-mod config;
-mod game;
-mod input;
-mod tile;
-mod utils;
-
-use bevy::{prelude::*, window::WindowResolution};
-use board::BoardPlugin;
-use config::{WINDOW_HEIGHT, WINDOW_WIDTH};
-use game::{GamePlugin, GameState};
-use input::InputPlugin;
-use tile::TilePlugin;
+use bevy::{
+    prelude::*,
+    window::{WindowResizeConstraints, WindowResolution},
+};
+use minesweeper::{
+    accessibility::AccessibilityPlugin,
+    analysis::AnalysisPlugin,
+    animation::AnimationPlugin,
+    autoplay::AutoplayPlugin,
+    board::BoardPlugin,
+    config::{MIN_WINDOW_HEIGHT, MIN_WINDOW_WIDTH, WINDOW_HEIGHT, WINDOW_WIDTH},
+    coop::CoopPlugin,
+    editor::EditorPlugin,
+    game::{GamePlugin, GameState},
+    hud::HudPlugin,
+    input::InputPlugin,
+    mode::ModePlugin,
+    net::NetConfig,
+    puzzle::PuzzlePlugin,
+    race::RacePlugin,
+    replay::ReplayPlugin,
+    settings::SettingsPlugin,
+    sound::SoundPlugin,
+    tile::TilePlugin,
+    transfer::TransferPlugin,
+    versus::VersusPlugin,
+};
+use std::{env, process};
 
 fn main() {
+    let net_config = match NetConfig::from_args(env::args().skip(1)) {
+        Ok(net_config) => net_config,
+        Err(err) => {
+            eprintln!("error: {err}\n\n{}", NetConfig::USAGE);
+            process::exit(2);
+        }
+    };
+
     App::new()
-        .add_plugins(
-            DefaultPlugins.set(WindowPlugin {
-                primary_window: Some(Window {
-                    title: "Minesweeper v1.0.1".into(),
-                    resolution: WindowResolution::new(WINDOW_WIDTH, WINDOW_HEIGHT)
-                        .with_scale_factor_override(1.0),
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "Minesweeper v1.0.1".into(),
+                resolution: WindowResolution::new(WINDOW_WIDTH, WINDOW_HEIGHT),
+                resize_constraints: WindowResizeConstraints {
+                    min_width: MIN_WINDOW_WIDTH,
+                    min_height: MIN_WINDOW_HEIGHT,
                     ..default()
-                }),
+                },
                 ..default()
             }),
-        )
+            ..default()
+        }))
         .init_state::<GameState>()
-        .add_plugins((GamePlugin, BoardPlugin, TilePlugin, InputPlugin))
+        .insert_resource(net_config)
+        .add_plugins((
+            GamePlugin,
+            BoardPlugin,
+            TilePlugin,
+            InputPlugin,
+            AccessibilityPlugin,
+            SoundPlugin,
+            SettingsPlugin,
+            AnimationPlugin,
+            HudPlugin,
+            ModePlugin,
+            PuzzlePlugin,
+            EditorPlugin,
+            TransferPlugin,
+            ReplayPlugin,
+            (
+                AnalysisPlugin,
+                AutoplayPlugin,
+                RacePlugin,
+                CoopPlugin,
+                VersusPlugin,
+            ),
+        ))
         .add_systems(Startup, setup_camera)
         .run();
 }
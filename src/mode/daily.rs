@@ -0,0 +1,262 @@
+use crate::{
+    board::{BoardSettings, grid::neighbour_table},
+    engine::Engine,
+    solver::solve_layout,
+    storage::{load_json, save_json},
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Mixed into the day number so daily boards differ from other seeded boards.
+const DAILY_SEED_SALT: u64 = 0x6d69_6e65_7377_6565;
+// Boards dealt looking for one without guesses before settling for the last.
+const MAX_DAILY_ROLLS: u32 = 1_000;
+
+/// Days since the Unix epoch in UTC, so everyone gets the same board on the same day.
+pub fn today() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| (elapsed.as_secs() / 86_400) as u32)
+}
+
+/// The seed for the day's `roll`th board; rolls sit above the day in the seed, so no two days
+/// share a board.
+pub fn daily_seed(day: u32, roll: u32) -> u64 {
+    DAILY_SEED_SALT ^ day as u64 ^ ((roll as u64) << 32)
+}
+
+/// The day's board: the default preset, dealt from the first roll that logic alone can clear
+/// from its opening cell, and fixed with that cell already open so the ranked attempt never
+/// comes down to a guess.
+pub fn daily_settings(day: u32) -> BoardSettings {
+    let mut settings = BoardSettings::default();
+    let neighbours = neighbour_table(&settings);
+    let mut board = None;
+    for roll in 0..MAX_DAILY_ROLLS {
+        settings.seed = Some(daily_seed(day, roll));
+        let mut engine = Engine::new(&settings);
+        let Some(cell) = engine.opening_cell() else {
+            continue;
+        };
+        engine.reveal(cell);
+        let layout = engine.layout();
+        let solved = solve_layout(&layout, &neighbours, settings.max_mines_per_cell()).solved;
+        board = Some(layout);
+        if solved {
+            break;
+        }
+    }
+    settings.layout = board;
+    settings
+}
+
+/// Formats a day number as `YYYY-MM-DD`.
+pub fn date_string(day: u32) -> String {
+    // Civil-from-days conversion over 400-year eras, counted from March so leap days fall last.
+    let days = day as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day_of_month = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day_of_month:02}")
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyResult {
+    pub day: u32,
+    pub date: String,
+    pub won: bool,
+    pub seconds: f32,
+}
+
+/// Ranked daily attempts, one per day; boards after the first are practice and not stored.
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DailyHistory {
+    pub results: Vec<DailyResult>,
+}
+
+impl DailyHistory {
+    const FILE_NAME: &'static str = "daily.json";
+
+    pub fn load() -> Self {
+        load_json(Self::FILE_NAME)
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = save_json(Self::FILE_NAME, self) {
+            warn!("Failed to save daily history: {err}");
+        }
+    }
+
+    pub fn result(&self, day: u32) -> Option<&DailyResult> {
+        self.results.iter().find(|result| result.day == day)
+    }
+
+    pub fn is_ranked(&self, day: u32) -> bool {
+        self.result(day).is_none()
+    }
+
+    /// Uses up the day's ranked attempt, stored as a loss until [`finish`](Self::finish) says
+    /// otherwise, so quitting or restarting halfway does not win another try.
+    pub fn start(&mut self, day: u32) {
+        if self.is_ranked(day) {
+            self.results.push(DailyResult {
+                day,
+                date: date_string(day),
+                won: false,
+                seconds: 0.0,
+            });
+        }
+    }
+
+    pub fn finish(&mut self, day: u32, won: bool, seconds: f32) {
+        if let Some(result) = self.results.iter_mut().find(|result| result.day == day) {
+            result.won = won;
+            result.seconds = seconds;
+        }
+    }
+
+    /// Consecutive daily wins ending today, or yesterday while today is still unplayed.
+    pub fn current_streak(&self, today: u32) -> u32 {
+        let mut day = if self.is_ranked(today) {
+            today.saturating_sub(1)
+        } else {
+            today
+        };
+
+        let mut streak = 0;
+        while self.result(day).is_some_and(|result| result.won) {
+            streak += 1;
+            if day == 0 {
+                break;
+            }
+            day -= 1;
+        }
+        streak
+    }
+
+    pub fn best_streak(&self) -> u32 {
+        let mut won_days: Vec<u32> = self
+            .results
+            .iter()
+            .filter(|result| result.won)
+            .map(|result| result.day)
+            .collect();
+        won_days.sort_unstable();
+
+        let mut best = 0;
+        let mut streak = 0;
+        let mut previous = None;
+        for day in won_days {
+            streak = if previous == Some(day.wrapping_sub(1)) {
+                streak + 1
+            } else {
+                1
+            };
+            best = best.max(streak);
+            previous = Some(day);
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_daily_board_solves_from_its_opening() {
+        for day in 20_000..20_010 {
+            let settings = daily_settings(day);
+            let layout = settings.layout.as_ref().expect("the daily board is fixed");
+            assert!(layout.revealed.iter().any(|revealed| *revealed));
+
+            let report = solve_layout(
+                layout,
+                &neighbour_table(&settings),
+                settings.max_mines_per_cell(),
+            );
+            assert!(report.solved, "day {day} needs a guess");
+        }
+    }
+
+    #[test]
+    fn the_daily_board_is_the_same_all_day() {
+        assert_eq!(daily_settings(20_000).layout, daily_settings(20_000).layout);
+        assert_ne!(daily_settings(20_000).layout, daily_settings(20_001).layout);
+    }
+
+    fn history(results: &[(u32, bool)]) -> DailyHistory {
+        DailyHistory {
+            results: results
+                .iter()
+                .map(|(day, won)| DailyResult {
+                    day: *day,
+                    date: date_string(*day),
+                    won: *won,
+                    seconds: 60.0,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn dates_follow_the_calendar() {
+        assert_eq!(date_string(0), "1970-01-01");
+        assert_eq!(date_string(19_782), "2024-02-29");
+        assert_eq!(date_string(19_783), "2024-03-01");
+        assert_eq!(date_string(11_016), "2000-02-29");
+        assert_eq!(date_string(47_540), "2100-02-28");
+        assert_eq!(date_string(47_541), "2100-03-01");
+    }
+
+    #[test]
+    fn dates_roll_over_into_the_new_year() {
+        assert_eq!(date_string(19_722), "2023-12-31");
+        assert_eq!(date_string(19_723), "2024-01-01");
+    }
+
+    #[test]
+    fn a_streak_runs_across_the_new_year() {
+        let history = history(&[(19_721, true), (19_722, true), (19_723, true)]);
+        assert_eq!(history.current_streak(19_723), 3);
+        assert_eq!(history.best_streak(), 3);
+    }
+
+    #[test]
+    fn a_missed_day_or_a_loss_breaks_the_streak() {
+        let history = history(&[
+            (100, true),
+            (101, true),
+            (102, true),
+            (104, true),
+            (105, false),
+            (106, true),
+        ]);
+        assert_eq!(history.current_streak(106), 1);
+        assert_eq!(history.current_streak(104), 1);
+        assert_eq!(history.best_streak(), 3);
+    }
+
+    #[test]
+    fn today_unplayed_keeps_yesterdays_streak() {
+        let unplayed = history(&[(200, true), (201, true)]);
+        assert_eq!(unplayed.current_streak(202), 2);
+        assert_eq!(unplayed.current_streak(203), 0);
+
+        let lost_today = history(&[(200, true), (201, true), (202, false)]);
+        assert_eq!(lost_today.current_streak(202), 0);
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(feature = "gui")]
+pub mod daily;
+pub mod records;
+#[cfg(feature = "gui")]
+pub mod resources;
+#[cfg(feature = "gui")]
+pub mod systems;
+
+#[cfg(feature = "gui")]
+use crate::{
+    board::systems::reset_board,
+    game::{GameState, events::NewGameEvent, systems::handle_new_game},
+};
+#[cfg(feature = "gui")]
+use bevy::prelude::*;
+#[cfg(feature = "gui")]
+use daily::DailyHistory;
+pub use records::*;
+#[cfg(feature = "gui")]
+pub use resources::*;
+#[cfg(feature = "gui")]
+use systems::*;
+
+#[cfg(feature = "gui")]
+pub struct ModePlugin;
+
+#[cfg(feature = "gui")]
+impl Plugin for ModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameMode>()
+            .init_resource::<ModeRun>()
+            .insert_resource(ModeRecords::load())
+            .insert_resource(DailyHistory::load())
+            .add_systems(
+                Update,
+                (start_run, apply_daily_preset)
+                    .chain()
+                    .run_if(on_event::<NewGameEvent>)
+                    .before(reset_board)
+                    .before(handle_new_game),
+            )
+            .add_systems(
+                Update,
+                (
+                    (score_rush_reveals, tick_elapsed).run_if(in_state(GameState::Playing)),
+                    claim_daily_attempt,
+                    record_board_result,
+                    tick_run_clock,
+                    chain_next_board,
+                    save_mode_records.run_if(resource_changed::<ModeRecords>),
+                    save_daily_history.run_if(resource_changed::<DailyHistory>),
+                )
+                    .chain(),
+            );
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{
+    game::GameStats,
+    storage::{load_json, save_json},
+};
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// What a run is scored on: one board, boards cleared against a countdown, cells opened
+/// while reveals buy time and mines cost it, the shared board of the day, a hand-made
+/// puzzle, or mines found by two players taking turns.
+#[cfg_attr(feature = "gui", derive(bevy::prelude::Resource))]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum GameMode {
+    #[default]
+    Classic,
+    TimeAttack,
+    Rush,
+    Daily,
+    Puzzle,
+    Versus,
+}
+
+impl GameMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Classic => "Classic",
+            GameMode::TimeAttack => "Time Attack",
+            GameMode::Rush => "Rush",
+            GameMode::Daily => "Daily",
+            GameMode::Puzzle => "Puzzle",
+            GameMode::Versus => "Versus",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            GameMode::Classic => GameMode::TimeAttack,
+            GameMode::TimeAttack => GameMode::Rush,
+            GameMode::Rush => GameMode::Daily,
+            GameMode::Daily => GameMode::Puzzle,
+            GameMode::Puzzle => GameMode::Versus,
+            GameMode::Versus => GameMode::Classic,
+        }
+    }
+
+    pub fn is_timed(&self) -> bool {
+        matches!(self, GameMode::TimeAttack | GameMode::Rush)
+    }
+
+    /// Lives would make daily results incomparable and puzzles trivial, so both ignore them,
+    /// and in versus a mine scores instead.
+    pub fn allows_lives(&self) -> bool {
+        !matches!(self, GameMode::Daily | GameMode::Puzzle | GameMode::Versus)
+    }
+
+    /// Mines found are flagged in place and play carries on.
+    pub fn claims_mines(&self) -> bool {
+        matches!(self, GameMode::Rush | GameMode::Versus)
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModeRecord {
+    pub runs: u32,
+    pub best_score: u32,
+    pub total_score: u32,
+    pub best_three_bv_per_second: f32,
+    pub best_ioe: f32,
+}
+
+impl ModeRecord {
+    fn add_win(&mut self, game_stats: &GameStats) {
+        self.best_three_bv_per_second = self
+            .best_three_bv_per_second
+            .max(game_stats.three_bv_per_second());
+        self.best_ioe = self.best_ioe.max(game_stats.ioe());
+    }
+
+    fn add(&mut self, score: u32) {
+        self.runs += 1;
+        self.best_score = self.best_score.max(score);
+        self.total_score += score;
+    }
+}
+
+/// Lifetime results per mode. Classic, ranked daily and puzzle boards score one per win, time
+/// attack counts boards cleared, rush counts cells opened and versus scores one per game won
+/// over the network.
+#[cfg_attr(feature = "gui", derive(bevy::prelude::Resource))]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModeRecords {
+    pub classic: ModeRecord,
+    pub time_attack: ModeRecord,
+    pub rush: ModeRecord,
+    pub daily: ModeRecord,
+    pub puzzle: ModeRecord,
+    pub versus: ModeRecord,
+}
+
+impl ModeRecords {
+    const FILE_NAME: &'static str = "mode_records.json";
+
+    pub fn load() -> Self {
+        load_json(Self::FILE_NAME)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        save_json(Self::FILE_NAME, self)
+    }
+
+    pub fn get(&self, mode: GameMode) -> &ModeRecord {
+        match mode {
+            GameMode::Classic => &self.classic,
+            GameMode::TimeAttack => &self.time_attack,
+            GameMode::Rush => &self.rush,
+            GameMode::Daily => &self.daily,
+            GameMode::Puzzle => &self.puzzle,
+            GameMode::Versus => &self.versus,
+        }
+    }
+
+    /// Keeps the best speed and efficiency of a cleared board.
+    pub fn record_win(&mut self, mode: GameMode, game_stats: &GameStats) {
+        self.get_mut(mode).add_win(game_stats);
+    }
+
+    fn get_mut(&mut self, mode: GameMode) -> &mut ModeRecord {
+        match mode {
+            GameMode::Classic => &mut self.classic,
+            GameMode::TimeAttack => &mut self.time_attack,
+            GameMode::Rush => &mut self.rush,
+            GameMode::Daily => &mut self.daily,
+            GameMode::Puzzle => &mut self.puzzle,
+            GameMode::Versus => &mut self.versus,
+        }
+    }
+
+    pub fn record(&mut self, mode: GameMode, score: u32) {
+        match mode {
+            GameMode::Classic => self.classic.add(score),
+            GameMode::TimeAttack => self.time_attack.add(score),
+            GameMode::Rush => self.rush.add(score),
+            GameMode::Daily => self.daily.add(score),
+            GameMode::Puzzle => self.puzzle.add(score),
+            GameMode::Versus => self.versus.add(score),
+        }
+    }
+}
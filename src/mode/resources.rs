@@ -0,0 +1,55 @@
+use crate::mode::{GameMode, daily::today};
+use bevy::prelude::*;
+
+pub const TIME_ATTACK_SECONDS: f32 = 180.0;
+pub const RUSH_START_SECONDS: f32 = 30.0;
+pub const RUSH_REVEAL_BONUS: f32 = 0.5;
+pub const RUSH_MINE_PENALTY: f32 = 10.0;
+pub const CHAIN_DELAY: f32 = 1.0;
+
+#[derive(Debug, PartialEq)]
+pub enum RunPhase {
+    Running,
+    /// The board just ended and the next one starts when the timer runs out.
+    Chaining(Timer),
+    Finished,
+}
+
+/// Progress through the current run, which spans several boards in the timed modes.
+#[derive(Resource, Debug)]
+pub struct ModeRun {
+    pub mode: GameMode,
+    pub phase: RunPhase,
+    pub clock: f32,
+    pub elapsed: f32,
+    pub score: u32,
+    pub day: u32,
+    /// This board is the day's ranked daily attempt, which its result is stored against.
+    pub ranked: bool,
+}
+
+impl Default for ModeRun {
+    fn default() -> Self {
+        Self::new(GameMode::Classic)
+    }
+}
+
+impl ModeRun {
+    pub fn new(mode: GameMode) -> Self {
+        let clock = match mode {
+            GameMode::Classic | GameMode::Daily | GameMode::Puzzle | GameMode::Versus => 0.0,
+            GameMode::TimeAttack => TIME_ATTACK_SECONDS,
+            GameMode::Rush => RUSH_START_SECONDS,
+        };
+
+        Self {
+            mode,
+            phase: RunPhase::Running,
+            clock,
+            elapsed: 0.0,
+            score: 0,
+            day: today(),
+            ranked: false,
+        }
+    }
+}
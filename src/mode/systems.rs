@@ -0,0 +1,201 @@
+use crate::{
+    board::BoardSettings,
+    game::{GameState, GameStats, components::OverlayText, events::*, systems::show_overlay_text},
+    mode::{
+        daily::{DailyHistory, daily_settings},
+        *,
+    },
+    tile::Tile,
+};
+use bevy::prelude::*;
+
+pub fn start_run(mode: Res<GameMode>, mut run: ResMut<ModeRun>, mut records: ResMut<ModeRecords>) {
+    // A ranked daily board left unfinished counts as lost.
+    if run.ranked {
+        records.record(GameMode::Daily, 0);
+    }
+
+    // Boards chained inside a timed run keep its clock and score.
+    if matches!(run.phase, RunPhase::Chaining(_)) && run.mode == *mode {
+        run.phase = RunPhase::Running;
+    } else {
+        *run = ModeRun::new(*mode);
+    }
+}
+
+/// The daily board always uses the default preset, seeded by the date and opened where logic
+/// can take over; it is looked for once per day.
+pub fn apply_daily_preset(
+    run: Res<ModeRun>,
+    mut settings: ResMut<BoardSettings>,
+    mut daily: Local<Option<(u32, BoardSettings)>>,
+) {
+    if run.mode == GameMode::Daily {
+        let board = match &*daily {
+            Some((day, board)) if *day == run.day => board,
+            _ => &daily.insert((run.day, daily_settings(run.day))).1,
+        };
+        *settings = BoardSettings {
+            tile_size: settings.tile_size,
+            ..board.clone()
+        };
+    } else if settings.seed.is_some() {
+        settings.seed = None;
+        settings.layout = None;
+    }
+}
+
+/// The first cell opened on a daily board uses up the day's ranked attempt, and so does any move
+/// the solver plays, which forfeits it as a loss; restarting or handing the board to the demo
+/// cannot fish for a better start.
+pub fn claim_daily_attempt(
+    mut run: ResMut<ModeRun>,
+    mut daily_history: ResMut<DailyHistory>,
+    mut records: ResMut<ModeRecords>,
+    game_stats: Res<GameStats>,
+    mut tile_revealed_events: EventReader<TileRevealedEvent>,
+) {
+    let revealed = tile_revealed_events.read().count() > 0;
+    if run.mode != GameMode::Daily {
+        return;
+    }
+
+    if game_stats.assisted {
+        if run.ranked {
+            run.ranked = false;
+            records.record(GameMode::Daily, 0);
+        } else if daily_history.is_ranked(run.day) {
+            daily_history.start(run.day);
+            records.record(GameMode::Daily, 0);
+        }
+    } else if revealed && daily_history.is_ranked(run.day) {
+        daily_history.start(run.day);
+        run.ranked = true;
+    }
+}
+
+pub fn tick_elapsed(time: Res<Time>, mut run: ResMut<ModeRun>) {
+    run.elapsed += time.delta_secs();
+}
+
+pub fn score_rush_reveals(
+    mut run: ResMut<ModeRun>,
+    mut mine_hit_events: EventReader<MineHitEvent>,
+    tile_query: Query<&Tile, Changed<Tile>>,
+) {
+    let mines_hit = mine_hit_events.read().count();
+    if run.mode != GameMode::Rush || run.phase != RunPhase::Running {
+        return;
+    }
+
+    let opened = tile_query
+        .iter()
+        .filter(|tile| tile.is_revealed && !tile.is_mine())
+        .count() as u32;
+
+    run.score += opened;
+    run.clock += opened as f32 * RUSH_REVEAL_BONUS - mines_hit as f32 * RUSH_MINE_PENALTY;
+}
+
+pub fn record_board_result(
+    mut run: ResMut<ModeRun>,
+    mut records: ResMut<ModeRecords>,
+    mut daily_history: ResMut<DailyHistory>,
+    game_stats: Res<GameStats>,
+    mut game_won_events: EventReader<GameWonEvent>,
+    mut game_over_events: EventReader<GameOverEvent>,
+) {
+    let won = game_won_events.read().count() > 0;
+    let lost = game_over_events.read().count() > 0;
+    if !won && !lost {
+        return;
+    }
+
+    // Boards the solver played are not the player's to keep.
+    let ranked = !game_stats.assisted;
+    if won && ranked {
+        records.record_win(run.mode, &game_stats);
+    }
+
+    if matches!(run.mode, GameMode::Classic | GameMode::Puzzle) {
+        if ranked {
+            records.record(run.mode, won as u32);
+        }
+    } else if run.mode == GameMode::Daily {
+        // Only the day's first attempt counts, see `claim_daily_attempt`; later boards are
+        // practice.
+        if run.ranked {
+            run.ranked = false;
+            daily_history.finish(run.day, won, run.elapsed);
+            records.record(GameMode::Daily, won as u32);
+        }
+    } else if run.phase == RunPhase::Running {
+        if won && run.mode == GameMode::TimeAttack {
+            run.score += 1;
+        }
+        run.phase = RunPhase::Chaining(Timer::from_seconds(CHAIN_DELAY, TimerMode::Once));
+    }
+}
+
+pub fn tick_run_clock(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut run: ResMut<ModeRun>,
+    mut records: ResMut<ModeRecords>,
+    mut next_state: ResMut<NextState<GameState>>,
+    overlay_entities: Query<Entity, With<OverlayText>>,
+) {
+    if !run.mode.is_timed() || run.phase == RunPhase::Finished {
+        return;
+    }
+
+    run.clock -= time.delta_secs();
+    if run.clock > 0.0 {
+        return;
+    }
+
+    run.clock = 0.0;
+    run.phase = RunPhase::Finished;
+    records.record(run.mode, run.score);
+    next_state.set(GameState::GameOver);
+
+    let best = records.get(run.mode).best_score;
+    let summary = match run.mode {
+        GameMode::Rush => format!("Out of time! {} cells opened", run.score),
+        _ => format!("Time up! {} boards cleared", run.score),
+    };
+
+    for entity in overlay_entities.iter() {
+        commands.entity(entity).despawn();
+    }
+    show_overlay_text(
+        &mut commands,
+        format!("{summary} (best {best}). Press R to restart"),
+    );
+}
+
+pub fn chain_next_board(
+    time: Res<Time>,
+    mut run: ResMut<ModeRun>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+) {
+    if let RunPhase::Chaining(timer) = &mut run.phase
+        && timer.tick(time.delta()).just_finished()
+    {
+        new_game_events.write(NewGameEvent);
+    }
+}
+
+pub fn save_mode_records(records: Res<ModeRecords>) {
+    if !records.is_added()
+        && let Err(err) = records.save()
+    {
+        warn!("Failed to save mode records: {err}");
+    }
+}
+
+pub fn save_daily_history(daily_history: Res<DailyHistory>) {
+    if !daily_history.is_added() {
+        daily_history.save();
+    }
+}
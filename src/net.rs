@@ -0,0 +1,312 @@
+//! One JSON message per line over TCP, so instances on a LAN can play together. Players only
+//! talk to the host, which relays what the others need to know.
+
+use crate::{board::BoardSettings, coop::CoopNet, race::RaceNet, versus::VersusNet};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, Receiver},
+    },
+    thread,
+};
+
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// The host is player 0; the others are numbered as they join.
+pub type PeerId = u8;
+pub const HOST: PeerId = 0;
+
+/// How this instance takes part, from the command line.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum NetRole {
+    #[default]
+    Offline,
+    Host {
+        port: u16,
+    },
+    Join {
+        address: String,
+    },
+}
+
+/// What is played over the connection; the host and the players joining must agree.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum NetGame {
+    /// Everyone plays their own copy of one board, and the first to clear it wins.
+    #[default]
+    Race,
+    /// Everyone plays the same board together, and wins or loses together.
+    Coop,
+    /// Two players take turns on one board to find the most mines; offline it is hot-seat.
+    Versus,
+}
+
+#[derive(Resource, Clone, Debug, Default)]
+pub struct NetConfig {
+    pub role: NetRole,
+    pub game: NetGame,
+    pub name: String,
+}
+
+impl NetConfig {
+    pub const USAGE: &'static str = "Usage: minesweeper [--host [--port N] | --join ADDRESS[:PORT]] [--coop | --versus] [--name NAME]";
+
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut host = false;
+        let mut port = DEFAULT_PORT;
+        let mut join = None;
+        let mut game = NetGame::Race;
+        let mut name = None;
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+            match arg.as_str() {
+                "--host" => host = true,
+                "--port" => {
+                    let value = value()?;
+                    port = value
+                        .parse()
+                        .map_err(|_| format!("--port expects a number, got '{value}'"))?;
+                }
+                "--join" => join = Some(value()?),
+                "--coop" => game = NetGame::Coop,
+                "--versus" => game = NetGame::Versus,
+                "--name" => name = Some(value()?),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        let role = match (host, join) {
+            (true, Some(_)) => return Err("--host and --join do not go together".to_string()),
+            (true, None) => NetRole::Host { port },
+            (false, Some(address)) if address.contains(':') => NetRole::Join { address },
+            (false, Some(address)) => NetRole::Join {
+                address: format!("{address}:{port}"),
+            },
+            (false, None) if game == NetGame::Coop => {
+                return Err("--coop needs --host or --join".to_string());
+            }
+            (false, None) => NetRole::Offline,
+        };
+        let name = name
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .unwrap_or_else(|| "Player".to_string());
+
+        Ok(Self { role, game, name })
+    }
+}
+
+/// Whether a network game of any kind is connected, where only the players may play: the
+/// solver would let one of them win a race or a versus board unaided.
+pub fn playing_online(race: Res<RaceNet>, coop: Res<CoopNet>, versus: Res<VersusNet>) -> bool {
+    !matches!(*race, RaceNet::Off)
+        || !matches!(*coop, CoopNet::Off)
+        || !matches!(*versus, VersusNet::Off)
+}
+
+/// The board every player is dealt; the seed deals the same mines for all of them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SharedBoard {
+    pub width: u8,
+    pub height: u8,
+    pub mines: u8,
+    pub multi_mine: bool,
+    pub seed: u64,
+}
+
+impl SharedBoard {
+    pub fn from_settings(settings: &BoardSettings, seed: u64) -> Self {
+        Self {
+            width: settings.width,
+            height: settings.height,
+            mines: settings.mine_count,
+            multi_mine: settings.multi_mine,
+            seed,
+        }
+    }
+
+    /// A plain rectangle like the daily board, so no player's shape or mask settings leak in.
+    /// A board that could not be dealt, such as one with more mines than cells, is ignored.
+    pub fn apply(&self, settings: &mut BoardSettings) {
+        let shared = BoardSettings {
+            width: self.width,
+            height: self.height,
+            mine_count: self.mines,
+            multi_mine: self.multi_mine,
+            seed: Some(self.seed),
+            tile_size: settings.tile_size,
+            ..default()
+        };
+        match shared.validate() {
+            Ok(()) => *settings = shared,
+            Err(err) => warn!("Ignoring the shared board: {err}"),
+        }
+    }
+}
+
+pub enum NetEvent<M> {
+    Joined(PeerId),
+    Message(PeerId, M),
+    Left(PeerId),
+}
+
+/// Accepts players in the background; their messages queue up until polled.
+pub struct Host<M> {
+    pub port: u16,
+    peers: Arc<Mutex<Vec<(PeerId, TcpStream)>>>,
+    events: Mutex<Receiver<NetEvent<M>>>,
+}
+
+impl<M: Serialize + DeserializeOwned + Send + 'static> Host<M> {
+    /// Listens on every interface, turning away players beyond `max_peers`.
+    pub fn bind(port: u16, max_peers: usize) -> io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        let port = listener.local_addr()?.port();
+        let peers = Arc::new(Mutex::new(Vec::new()));
+        let (sender, events) = mpsc::channel();
+
+        let joined = peers.clone();
+        thread::spawn(move || {
+            let mut next_peer: PeerId = HOST + 1;
+            for stream in listener.incoming().flatten() {
+                let mut peers = joined.lock().unwrap();
+                // Dropping the stream closes it.
+                if peers.len() >= max_peers || next_peer == PeerId::MAX {
+                    continue;
+                }
+                let Ok(reader) = stream.try_clone() else {
+                    continue;
+                };
+                let _ = stream.set_nodelay(true);
+
+                let peer = next_peer;
+                next_peer += 1;
+                peers.push((peer, stream));
+                drop(peers);
+                if sender.send(NetEvent::Joined(peer)).is_err() {
+                    break;
+                }
+
+                let sender = sender.clone();
+                let peers = joined.clone();
+                thread::spawn(move || {
+                    read_messages(reader, |message| {
+                        sender.send(NetEvent::Message(peer, message)).is_ok()
+                    });
+                    peers.lock().unwrap().retain(|(other, _)| *other != peer);
+                    let _ = sender.send(NetEvent::Left(peer));
+                });
+            }
+        });
+
+        Ok(Self {
+            port,
+            peers,
+            events: Mutex::new(events),
+        })
+    }
+
+    pub fn poll(&self) -> Vec<NetEvent<M>> {
+        self.events.lock().unwrap().try_iter().collect()
+    }
+
+    pub fn send(&self, peer: PeerId, message: &M) {
+        let line = encode(message);
+        for (other, stream) in self.peers.lock().unwrap().iter_mut() {
+            if *other == peer {
+                write_line(stream, &line);
+            }
+        }
+    }
+
+    pub fn broadcast(&self, message: &M) {
+        let line = encode(message);
+        for (_, stream) in self.peers.lock().unwrap().iter_mut() {
+            write_line(stream, &line);
+        }
+    }
+}
+
+/// A connection to a host; its messages queue up until polled.
+pub struct Client<M> {
+    stream: Mutex<TcpStream>,
+    events: Mutex<Receiver<Option<M>>>,
+}
+
+impl<M: Serialize + DeserializeOwned + Send + 'static> Client<M> {
+    pub fn connect(address: &str) -> io::Result<Self> {
+        let stream = TcpStream::connect(address)?;
+        stream.set_nodelay(true)?;
+        let reader = stream.try_clone()?;
+        let (sender, events) = mpsc::channel();
+
+        thread::spawn(move || {
+            read_messages(reader, |message| sender.send(Some(message)).is_ok());
+            let _ = sender.send(None);
+        });
+
+        Ok(Self {
+            stream: Mutex::new(stream),
+            events: Mutex::new(events),
+        })
+    }
+
+    /// Messages from the host, with `None` once it has gone.
+    pub fn poll(&self) -> Vec<Option<M>> {
+        self.events.lock().unwrap().try_iter().collect()
+    }
+
+    pub fn send(&self, message: &M) {
+        write_line(&mut self.stream.lock().unwrap(), &encode(message));
+    }
+}
+
+/// Hangs up on every player; their readers then report them gone.
+impl<M> Drop for Host<M> {
+    fn drop(&mut self) {
+        for (_, stream) in self.peers.lock().unwrap().iter() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+/// The reader thread holds a clone of the stream, so dropping ours alone would not close it.
+impl<M> Drop for Client<M> {
+    fn drop(&mut self) {
+        let _ = self.stream.lock().unwrap().shutdown(Shutdown::Both);
+    }
+}
+
+fn encode<M: Serialize>(message: &M) -> String {
+    let mut line = serde_json::to_string(message).unwrap_or_default();
+    line.push('\n');
+    line
+}
+
+/// A failed write closes the connection, which its reader then reports.
+fn write_line(stream: &mut TcpStream, line: &str) {
+    if stream.write_all(line.as_bytes()).is_err() {
+        let _ = stream.shutdown(Shutdown::Both);
+    }
+}
+
+/// Hands each message to `handle` until the connection closes or `handle` returns false.
+/// Lines that do not parse are skipped.
+fn read_messages<M: DeserializeOwned>(stream: TcpStream, mut handle: impl FnMut(M) -> bool) {
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            return;
+        };
+        if let Ok(message) = serde_json::from_str(&line)
+            && !handle(message)
+        {
+            return;
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! The board options the command line tools share.
+
+use crate::board::{BoardSettings, Preset};
+use std::str::FromStr;
+
+/// `--preset`, `--width`, `--height`, `--mines`, `--seed` and `--multi-mine`.
+#[derive(Default)]
+pub struct BoardOptions {
+    pub preset: Option<Preset>,
+    pub width: Option<u8>,
+    pub height: Option<u8>,
+    pub mines: Option<u8>,
+    pub seed: Option<u64>,
+    pub multi_mine: bool,
+}
+
+impl BoardOptions {
+    /// Reads `arg`, and its value from `args`, if it is a board option; returns whether it was.
+    pub fn parse_arg<'a>(
+        &mut self,
+        arg: &str,
+        args: &mut impl Iterator<Item = &'a String>,
+    ) -> Result<bool, String> {
+        match arg {
+            "--preset" => {
+                let name = args.next().ok_or(format!("{arg} needs a value"))?;
+                self.preset =
+                    Some(Preset::from_name(name).ok_or(format!("unknown preset '{name}'"))?);
+            }
+            "--width" => self.width = Some(number_arg(arg, args)?),
+            "--height" => self.height = Some(number_arg(arg, args)?),
+            "--mines" => self.mines = Some(number_arg(arg, args)?),
+            "--seed" => self.seed = Some(number_arg(arg, args)?),
+            "--multi-mine" => self.multi_mine = true,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Whether the size or mine count was given by hand rather than taken from a preset.
+    pub fn is_custom(&self) -> bool {
+        self.width.is_some() || self.height.is_some() || self.mines.is_some()
+    }
+
+    /// `preset`'s board with the options given on top of it, if the mines fit.
+    pub fn settings(&self, preset: Preset) -> Result<BoardSettings, String> {
+        let mut settings = preset.settings();
+        settings.width = self.width.unwrap_or(settings.width);
+        settings.height = self.height.unwrap_or(settings.height);
+        settings.mine_count = self.mines.unwrap_or(settings.mine_count);
+        settings.multi_mine = self.multi_mine;
+        settings.seed = self.seed;
+
+        settings.validate()?;
+        Ok(settings)
+    }
+}
+
+/// Parses the value following `name` in `args` as a number.
+pub fn number_arg<'a, T: FromStr>(
+    name: &str,
+    args: &mut impl Iterator<Item = &'a String>,
+) -> Result<T, String> {
+    let value = args.next().ok_or(format!("{name} needs a value"))?;
+    value
+        .parse()
+        .map_err(|_| format!("{name} expects a number, got '{value}'"))
+}
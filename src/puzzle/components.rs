@@ -0,0 +1,11 @@
+use crate::puzzle::CurrentLevel;
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct LevelSelectPanel;
+
+#[derive(Component)]
+pub struct LevelButton(pub CurrentLevel);
+
+#[derive(Component)]
+pub struct LevelLabel(pub CurrentLevel);
@@ -0,0 +1,45 @@
+pub mod components;
+pub mod resources;
+pub mod systems;
+
+use crate::{
+    board::systems::reset_board,
+    game::{events::NewGameEvent, systems::handle_new_game},
+    mode::systems::{apply_daily_preset, start_run},
+    net::playing_online,
+};
+use bevy::prelude::*;
+pub use components::*;
+pub use resources::*;
+use systems::*;
+
+pub struct PuzzlePlugin;
+
+impl Plugin for PuzzlePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(PuzzleLibrary::load())
+            .insert_resource(PuzzleProgress::load())
+            .init_resource::<CurrentLevel>()
+            .add_systems(
+                Update,
+                apply_puzzle_level
+                    .run_if(on_event::<NewGameEvent>)
+                    .after(start_run)
+                    .before(apply_daily_preset)
+                    .before(reset_board)
+                    .before(handle_new_game),
+            )
+            .add_systems(
+                Update,
+                (
+                    spawn_level_select.run_if(resource_changed::<PuzzleLibrary>),
+                    toggle_level_select,
+                    handle_level_buttons.run_if(not(playing_online)),
+                    record_puzzle_completion,
+                    update_level_labels,
+                    save_puzzle_progress.run_if(resource_changed::<PuzzleProgress>),
+                )
+                    .chain(),
+            );
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{
+    board::layout::{BoardLayout, LayoutError},
+    storage::{data_dir, load_json, save_json},
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs};
+
+const BUILT_IN_PACKS: [&str; 1] = [include_str!("../../assets/levels/starter.json")];
+
+/// A hand-made board, written in the row format of [`BoardLayout::from_rows`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Level {
+    pub title: String,
+    pub par_seconds: u32,
+    pub rows: Vec<String>,
+}
+
+impl Level {
+    pub fn layout(&self) -> Result<BoardLayout, LayoutError> {
+        BoardLayout::from_rows(&self.title, &self.rows)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelPack {
+    pub name: String,
+    pub levels: Vec<Level>,
+}
+
+/// Built-in packs followed by any `.json` packs in the data directory's `levels` folder.
+#[derive(Resource, Default)]
+pub struct PuzzleLibrary {
+    pub packs: Vec<LevelPack>,
+}
+
+impl PuzzleLibrary {
+    pub fn load() -> Self {
+        let mut sources: Vec<(String, String)> = BUILT_IN_PACKS
+            .iter()
+            .map(|contents| ("built-in pack".to_string(), contents.to_string()))
+            .collect();
+
+        if let Ok(entries) = fs::read_dir(data_dir().join("levels")) {
+            let mut paths: Vec<_> = entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.extension()
+                        .is_some_and(|extension| extension == "json")
+                })
+                .collect();
+            paths.sort();
+
+            for path in paths {
+                match fs::read_to_string(&path) {
+                    Ok(contents) => sources.push((path.display().to_string(), contents)),
+                    Err(err) => warn!("Skipping level pack {}: {err}", path.display()),
+                }
+            }
+        }
+
+        let packs = sources
+            .into_iter()
+            .filter_map(|(source, contents)| {
+                let mut pack: LevelPack = serde_json::from_str(&contents)
+                    .inspect_err(|err| warn!("Skipping level pack {source}: {err}"))
+                    .ok()?;
+                pack.levels.retain(|level| {
+                    level
+                        .layout()
+                        .inspect_err(|err| warn!("Skipping level {}: {err}", level.title))
+                        .is_ok()
+                });
+                Some(pack)
+            })
+            .collect();
+
+        Self { packs }
+    }
+
+    pub fn level(&self, current: CurrentLevel) -> Option<&Level> {
+        self.packs.get(current.pack)?.levels.get(current.level)
+    }
+
+    pub fn key(&self, current: CurrentLevel) -> Option<String> {
+        let pack = self.packs.get(current.pack)?;
+        let level = pack.levels.get(current.level)?;
+        Some(format!("{}/{}", pack.name, level.title))
+    }
+}
+
+#[derive(Resource, Copy, Clone, Debug, Default, PartialEq)]
+pub struct CurrentLevel {
+    pub pack: usize,
+    pub level: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelResult {
+    pub best_seconds: f32,
+}
+
+/// Completed puzzles keyed by `pack/title`, so reordering a pack keeps progress.
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PuzzleProgress {
+    pub completed: BTreeMap<String, LevelResult>,
+}
+
+impl PuzzleProgress {
+    const FILE_NAME: &'static str = "puzzles.json";
+
+    pub fn load() -> Self {
+        load_json(Self::FILE_NAME)
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = save_json(Self::FILE_NAME, self) {
+            warn!("Failed to save puzzle progress: {err}");
+        }
+    }
+
+    pub fn record(&mut self, key: String, seconds: f32) {
+        let result = self.completed.entry(key).or_insert(LevelResult {
+            best_seconds: seconds,
+        });
+        result.best_seconds = result.best_seconds.min(seconds);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        board::{BoardSettings, grid::neighbour_table},
+        solver::solve_layout,
+    };
+
+    #[test]
+    fn every_built_in_level_solves_without_guessing() {
+        for contents in BUILT_IN_PACKS {
+            let pack: LevelPack = serde_json::from_str(contents).unwrap();
+            for level in &pack.levels {
+                let layout = level.layout().unwrap();
+                let mut settings = BoardSettings::default();
+                settings.apply_layout(layout.clone());
+
+                let report = solve_layout(
+                    &layout,
+                    &neighbour_table(&settings),
+                    settings.max_mines_per_cell(),
+                );
+                assert!(
+                    report.solved,
+                    "{} needs a guess after {} of {} cells",
+                    level.title, report.revealed_cells, report.safe_cells
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{
+    board::{BoardSettings, layout::BoardLayout},
+    game::events::*,
+    mode::{GameMode, ModeRun},
+    puzzle::*,
+};
+use bevy::prelude::*;
+
+const PANEL_FONT_SIZE: f32 = 14.0;
+
+/// Loads the selected level in puzzle mode, and puts the default board back on leaving it
+/// unless something else has replaced the board since.
+pub fn apply_puzzle_level(
+    run: Res<ModeRun>,
+    library: Res<PuzzleLibrary>,
+    current: Res<CurrentLevel>,
+    mut settings: ResMut<BoardSettings>,
+    mut applied: Local<Option<BoardLayout>>,
+) {
+    if run.mode == GameMode::Puzzle {
+        if let Some(layout) = library
+            .level(*current)
+            .and_then(|level| level.layout().ok())
+        {
+            *applied = Some(layout.clone());
+            settings.apply_layout(layout);
+        }
+    } else if let Some(layout) = applied.take()
+        && settings.layout.as_ref() == Some(&layout)
+    {
+        *settings = BoardSettings {
+            tile_size: settings.tile_size,
+            ..default()
+        };
+    }
+}
+
+/// Builds the level list, rebuilding it whenever the library is reloaded.
+pub fn spawn_level_select(
+    mut commands: Commands,
+    library: Res<PuzzleLibrary>,
+    panel_query: Query<(Entity, &Visibility), With<LevelSelectPanel>>,
+) {
+    let mut visibility = Visibility::Hidden;
+    for (entity, panel_visibility) in panel_query.iter() {
+        visibility = *panel_visibility;
+        commands.entity(entity).despawn();
+    }
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            Interaction::default(),
+            visibility,
+            LevelSelectPanel,
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(6.0),
+                    padding: UiRect::all(Val::Px(12.0)),
+                    max_height: Val::Percent(90.0),
+                    overflow: Overflow::clip_y(),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0., 0., 0., 0.9)),
+            ))
+            .with_children(|panel| {
+                panel.spawn(panel_text("Puzzles (L to close)".to_string()));
+
+                for (pack_index, pack) in library.packs.iter().enumerate() {
+                    panel.spawn(panel_text(pack.name.clone()));
+
+                    for level_index in 0..pack.levels.len() {
+                        let level = CurrentLevel {
+                            pack: pack_index,
+                            level: level_index,
+                        };
+
+                        panel
+                            .spawn((
+                                Button,
+                                Node {
+                                    padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                                    ..default()
+                                },
+                                BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+                                LevelButton(level),
+                            ))
+                            .with_child((panel_text(String::new()), LevelLabel(level)));
+                    }
+                }
+            });
+        });
+}
+
+fn panel_text(text: String) -> (Text, TextFont) {
+    (
+        Text::new(text),
+        TextFont {
+            font_size: PANEL_FONT_SIZE,
+            ..default()
+        },
+    )
+}
+
+fn format_seconds(seconds: f32) -> String {
+    let seconds = seconds as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+pub fn toggle_level_select(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    game_mode: Res<GameMode>,
+    mut panel: Single<&mut Visibility, With<LevelSelectPanel>>,
+) {
+    let opened_puzzles = game_mode.is_changed() && *game_mode == GameMode::Puzzle;
+
+    if keyboard_input.just_pressed(KeyCode::KeyL) || opened_puzzles {
+        **panel = match **panel {
+            Visibility::Hidden => Visibility::Visible,
+            _ if opened_puzzles => Visibility::Visible,
+            _ => Visibility::Hidden,
+        };
+    }
+}
+
+pub fn handle_level_buttons(
+    mut current: ResMut<CurrentLevel>,
+    mut game_mode: ResMut<GameMode>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+    mut panel: Single<&mut Visibility, With<LevelSelectPanel>>,
+    button_query: Query<(&Interaction, &LevelButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        *current = button.0;
+        game_mode.set_if_neq(GameMode::Puzzle);
+        new_game_events.write(NewGameEvent);
+        **panel = Visibility::Hidden;
+    }
+}
+
+pub fn record_puzzle_completion(
+    run: Res<ModeRun>,
+    library: Res<PuzzleLibrary>,
+    current: Res<CurrentLevel>,
+    mut progress: ResMut<PuzzleProgress>,
+    mut game_won_events: EventReader<GameWonEvent>,
+) {
+    for _ in game_won_events.read() {
+        if run.mode == GameMode::Puzzle
+            && let Some(key) = library.key(*current)
+        {
+            progress.record(key, run.elapsed);
+        }
+    }
+}
+
+pub fn update_level_labels(
+    library: Res<PuzzleLibrary>,
+    progress: Res<PuzzleProgress>,
+    new_labels: Query<(), Added<LevelLabel>>,
+    mut label_query: Query<(&LevelLabel, &mut Text)>,
+) {
+    if !progress.is_changed() && new_labels.is_empty() {
+        return;
+    }
+
+    for (label, mut text) in label_query.iter_mut() {
+        let Some(level) = library.level(label.0) else {
+            continue;
+        };
+        let result = library
+            .key(label.0)
+            .and_then(|key| progress.completed.get(&key));
+
+        text.0 = match result {
+            Some(result) => format!(
+                "[x] {}  par {}  best {}",
+                level.title,
+                format_seconds(level.par_seconds as f32),
+                format_seconds(result.best_seconds)
+            ),
+            None => format!(
+                "[ ] {}  par {}",
+                level.title,
+                format_seconds(level.par_seconds as f32)
+            ),
+        };
+    }
+}
+
+pub fn save_puzzle_progress(progress: Res<PuzzleProgress>) {
+    if !progress.is_added() {
+        progress.save();
+    }
+}
@@ -0,0 +1,46 @@
+pub mod protocol;
+pub mod resources;
+pub mod systems;
+
+use crate::{
+    board::systems::reset_board,
+    game::{events::NewGameEvent, systems::handle_new_game},
+    mode::systems::{apply_daily_preset, start_run},
+    net::NetConfig,
+};
+use bevy::prelude::*;
+pub use protocol::*;
+pub use resources::*;
+use systems::*;
+
+/// Races over the LAN: every player gets the same seeded board and the first to clear it
+/// wins.
+pub struct RacePlugin;
+
+impl Plugin for RacePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NetConfig>()
+            .init_resource::<RaceNet>()
+            .init_resource::<Race>()
+            .add_systems(Startup, open_race_connection)
+            .add_systems(
+                Update,
+                (
+                    apply_race_board
+                        .after(apply_daily_preset)
+                        .before(reset_board),
+                    apply_race_rules.after(handle_new_game),
+                )
+                    .run_if(on_event::<NewGameEvent>),
+            )
+            .add_systems(
+                Update,
+                (
+                    (receive_race_messages, start_race).before(start_run),
+                    // The board a new game replaces would report stale progress.
+                    report_race_progress.run_if(not(on_event::<NewGameEvent>)),
+                )
+                    .chain(),
+            );
+    }
+}
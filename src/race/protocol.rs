@@ -0,0 +1,60 @@
+use crate::net::{PeerId, SharedBoard};
+use serde::{Deserialize, Serialize};
+
+/// How far a player has got: the share of safe cells opened, in percent, and their time once
+/// the board is cleared.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Progress {
+    pub cleared: u8,
+    pub alive: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished: Option<f32>,
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self {
+            cleared: 0,
+            alive: true,
+            finished: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Racer {
+    pub id: PeerId,
+    pub name: String,
+    pub progress: Progress,
+    /// Finishing position, from 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub place: Option<u8>,
+}
+
+impl Racer {
+    pub fn new(id: PeerId, name: String) -> Self {
+        Self {
+            id,
+            name,
+            progress: Progress::default(),
+            place: None,
+        }
+    }
+
+    /// Still clearing the board.
+    pub fn is_running(&self) -> bool {
+        self.progress.alive && self.place.is_none()
+    }
+}
+
+/// Players send `Hello` and then their `Progress`; the host answers with `Welcome`, and
+/// sends everyone the `Standings` whenever they change and `Start` to begin a race.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RaceMessage {
+    Hello { name: String },
+    Welcome { player: PeerId },
+    Standings { racers: Vec<Racer>, racing: bool },
+    Start { board: SharedBoard },
+    Progress(Progress),
+}
@@ -0,0 +1,175 @@
+use crate::{
+    net::{Client, Host, PeerId, SharedBoard},
+    race::{Progress, RaceMessage, Racer},
+};
+use bevy::prelude::*;
+
+/// Up to eight players, the host included.
+pub const MAX_RACERS: usize = 8;
+
+#[derive(Resource, Default)]
+pub enum RaceNet {
+    #[default]
+    Off,
+    Host(Host<RaceMessage>),
+    Client(Client<RaceMessage>),
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct Race {
+    /// This player's id, once the host has assigned one.
+    pub me: Option<PeerId>,
+    pub racers: Vec<Racer>,
+    /// The board being raced on; `None` between races.
+    pub board: Option<SharedBoard>,
+    /// How the connection or the last race went, shown between races.
+    pub status: String,
+    /// The progress last reported, so it is only sent when it changes.
+    pub sent: Option<Progress>,
+}
+
+impl Race {
+    pub fn racing(&self) -> bool {
+        self.board.is_some()
+    }
+
+    pub fn racer_mut(&mut self, id: PeerId) -> Option<&mut Racer> {
+        self.racers.iter_mut().find(|racer| racer.id == id)
+    }
+
+    /// Takes a player's progress into the standings, placing them if they just finished.
+    /// Returns whether the standings changed.
+    pub fn record_progress(&mut self, id: PeerId, progress: Progress) -> bool {
+        if !self.racing() {
+            return false;
+        }
+        let next_place = self
+            .racers
+            .iter()
+            .filter(|racer| racer.place.is_some())
+            .count() as u8
+            + 1;
+        let Some(racer) = self.racer_mut(id) else {
+            return false;
+        };
+        // Dead and finished players are out of the race, whatever they do next.
+        if !racer.is_running() || racer.progress == progress {
+            return false;
+        }
+
+        racer.progress = progress;
+        if progress.alive && progress.finished.is_some() {
+            racer.place = Some(next_place);
+        }
+        self.end_if_over();
+        true
+    }
+
+    /// Ends the race once nobody is left clearing the board.
+    pub fn end_if_over(&mut self) {
+        if self.racing() && self.racers.iter().all(|racer| !racer.is_running()) {
+            self.end();
+        }
+    }
+
+    pub fn end(&mut self) {
+        self.board = None;
+        self.status = match self.racers.iter().find(|racer| racer.place == Some(1)) {
+            Some(winner) => format!("Race over: {} wins", winner.name),
+            None => "Race over: nobody cleared the board".to_string(),
+        };
+    }
+
+    /// Every player back at the start of `board`.
+    pub fn start(&mut self, board: SharedBoard) {
+        for racer in &mut self.racers {
+            *racer = Racer::new(racer.id, std::mem::take(&mut racer.name));
+        }
+        self.board = Some(board);
+        self.sent = None;
+        self.status.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn race(names: &[&str]) -> Race {
+        let mut race = Race::default();
+        for (id, name) in names.iter().enumerate() {
+            race.racers.push(Racer::new(id as PeerId, name.to_string()));
+        }
+        race.start(SharedBoard {
+            width: 9,
+            height: 9,
+            mines: 10,
+            multi_mine: false,
+            seed: 1,
+        });
+        race
+    }
+
+    fn finished(seconds: f32) -> Progress {
+        Progress {
+            cleared: 100,
+            alive: true,
+            finished: Some(seconds),
+        }
+    }
+
+    fn dead(cleared: u8) -> Progress {
+        Progress {
+            cleared,
+            alive: false,
+            finished: None,
+        }
+    }
+
+    fn places(race: &Race) -> Vec<Option<u8>> {
+        race.racers.iter().map(|racer| racer.place).collect()
+    }
+
+    #[test]
+    fn players_are_placed_in_the_order_they_finish() {
+        let mut race = race(&["alice", "bob", "carol"]);
+        assert!(race.record_progress(1, finished(20.0)));
+        assert!(race.record_progress(2, finished(25.0)));
+        assert_eq!(places(&race), vec![None, Some(1), Some(2)]);
+        assert!(race.racing());
+
+        assert!(race.record_progress(0, dead(40)));
+        assert_eq!(places(&race), vec![None, Some(1), Some(2)]);
+        assert!(!race.racing());
+        assert_eq!(race.status, "Race over: bob wins");
+    }
+
+    #[test]
+    fn finished_and_dead_players_keep_their_result() {
+        let mut race = race(&["alice", "bob", "carol"]);
+        race.record_progress(0, finished(20.0));
+        race.record_progress(1, dead(10));
+
+        assert!(!race.record_progress(0, dead(100)));
+        assert!(!race.record_progress(1, finished(30.0)));
+        assert_eq!(places(&race), vec![Some(1), None, None]);
+        assert_eq!(race.racers[1].progress, dead(10));
+    }
+
+    #[test]
+    fn progress_outside_a_race_is_ignored() {
+        let mut race = race(&["alice"]);
+        race.end();
+        assert!(!race.record_progress(0, finished(20.0)));
+        assert_eq!(places(&race), vec![None]);
+    }
+
+    #[test]
+    fn a_race_nobody_finishes_has_no_winner() {
+        let mut race = race(&["alice", "bob"]);
+        race.record_progress(0, dead(50));
+        race.record_progress(1, dead(70));
+        assert!(!race.racing());
+        assert_eq!(race.status, "Race over: nobody cleared the board");
+    }
+}
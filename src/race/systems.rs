@@ -0,0 +1,210 @@
+use crate::{
+    board::BoardSettings,
+    game::{GameState, GameStats, Lives, events::NewGameEvent},
+    mode::GameMode,
+    net::{Client, HOST, Host, NetConfig, NetEvent, NetGame, NetRole, SharedBoard},
+    race::*,
+    tile::Tile,
+};
+use bevy::prelude::*;
+
+pub fn open_race_connection(
+    config: Res<NetConfig>,
+    mut net: ResMut<RaceNet>,
+    mut race: ResMut<Race>,
+) {
+    if config.game != NetGame::Race {
+        return;
+    }
+    match &config.role {
+        NetRole::Offline => {}
+        NetRole::Host { port } => match Host::bind(*port, MAX_RACERS - 1) {
+            Ok(host) => {
+                race.me = Some(HOST);
+                race.racers = vec![Racer::new(HOST, config.name.clone())];
+                race.status = format!("Hosting on port {}", host.port);
+                *net = RaceNet::Host(host);
+            }
+            Err(err) => race.status = format!("Could not host on port {port}: {err}"),
+        },
+        NetRole::Join { address } => match Client::connect(address) {
+            Ok(client) => {
+                client.send(&RaceMessage::Hello {
+                    name: config.name.clone(),
+                });
+                race.status = format!("Joined {address}, waiting for the host");
+                *net = RaceNet::Client(client);
+            }
+            Err(err) => race.status = format!("Could not join {address}: {err}"),
+        },
+    }
+}
+
+fn broadcast_standings(host: &Host<RaceMessage>, race: &Race) {
+    host.broadcast(&RaceMessage::Standings {
+        racers: race.racers.clone(),
+        racing: race.racing(),
+    });
+}
+
+/// Everyone races in Classic mode, so the board ends with the first mine.
+fn begin_race(
+    board: SharedBoard,
+    race: &mut Race,
+    mode: &mut GameMode,
+    new_game_events: &mut EventWriter<NewGameEvent>,
+) {
+    race.start(board);
+    *mode = GameMode::Classic;
+    new_game_events.write(NewGameEvent);
+}
+
+/// The host keeps the standings and relays them; players follow the host.
+pub fn receive_race_messages(
+    mut net: ResMut<RaceNet>,
+    mut race: ResMut<Race>,
+    mut mode: ResMut<GameMode>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+) {
+    let mut host_left = false;
+    match &*net {
+        RaceNet::Off => {}
+        RaceNet::Host(host) => {
+            let mut changed = false;
+            for event in host.poll() {
+                match event {
+                    NetEvent::Message(id, RaceMessage::Hello { name }) => {
+                        if race.racer_mut(id).is_none() {
+                            let mut racer = Racer::new(id, name);
+                            // Players joining mid-race wait for the next one.
+                            racer.progress.alive = !race.racing();
+                            race.racers.push(racer);
+                        }
+                        host.send(id, &RaceMessage::Welcome { player: id });
+                        changed = true;
+                    }
+                    NetEvent::Message(id, RaceMessage::Progress(progress)) => {
+                        changed |= race.record_progress(id, progress);
+                    }
+                    NetEvent::Left(id) => {
+                        race.racers.retain(|racer| racer.id != id);
+                        race.end_if_over();
+                        changed = true;
+                    }
+                    NetEvent::Joined(_) | NetEvent::Message(..) => {}
+                }
+            }
+            if changed {
+                broadcast_standings(host, &race);
+            }
+        }
+        RaceNet::Client(client) => {
+            for message in client.poll() {
+                match message {
+                    Some(RaceMessage::Welcome { player }) => race.me = Some(player),
+                    Some(RaceMessage::Standings { racers, racing }) => {
+                        race.racers = racers;
+                        if race.racing() && !racing {
+                            race.end();
+                        }
+                    }
+                    Some(RaceMessage::Start { board }) => {
+                        begin_race(board, &mut race, &mut mode, &mut new_game_events);
+                    }
+                    Some(_) => {}
+                    None => host_left = true,
+                }
+            }
+        }
+    }
+
+    if host_left {
+        *net = RaceNet::Off;
+        race.board = None;
+        race.status = "The host has left".to_string();
+    }
+}
+
+/// F5 on the host deals everyone a fresh board of the host's size.
+pub fn start_race(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    net: Res<RaceNet>,
+    settings: Res<BoardSettings>,
+    mut race: ResMut<Race>,
+    mut mode: ResMut<GameMode>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+) {
+    let RaceNet::Host(host) = &*net else {
+        return;
+    };
+    if !keyboard_input.just_pressed(KeyCode::F5) || race.racing() {
+        return;
+    }
+    if race.racers.len() < 2 {
+        race.status = "Waiting for another player to join".to_string();
+        return;
+    }
+
+    let board = SharedBoard::from_settings(&settings, rand::random());
+    host.broadcast(&RaceMessage::Start {
+        board: board.clone(),
+    });
+    begin_race(board, &mut race, &mut mode, &mut new_game_events);
+    broadcast_standings(host, &race);
+}
+
+/// Every new game during a race, restarts included, deals the race's board.
+pub fn apply_race_board(race: Res<Race>, mut settings: ResMut<BoardSettings>) {
+    if let Some(board) = &race.board {
+        board.apply(&mut settings);
+    }
+}
+
+/// Spare lives would let a player outrun the others by hitting mines.
+pub fn apply_race_rules(race: Res<Race>, mut lives: ResMut<Lives>) {
+    if race.racing() {
+        lives.remaining = 0;
+    }
+}
+
+pub fn report_race_progress(
+    net: Res<RaceNet>,
+    mut race: ResMut<Race>,
+    state: Res<State<GameState>>,
+    game_stats: Res<GameStats>,
+    tile_query: Query<&Tile>,
+) {
+    let Some(me) = race.me else {
+        return;
+    };
+    if !race.racing() {
+        return;
+    }
+
+    let safe_tiles = tile_query.iter().filter(|tile| !tile.is_mine()).count();
+    let revealed_tiles = tile_query
+        .iter()
+        .filter(|tile| tile.is_revealed && !tile.is_mine())
+        .count();
+    let progress = Progress {
+        cleared: (revealed_tiles * 100 / safe_tiles.max(1)) as u8,
+        alive: *state.get() != GameState::GameOver,
+        // A board the solver helped with is not a finish.
+        finished: (*state.get() == GameState::Won && !game_stats.assisted)
+            .then_some(game_stats.seconds),
+    };
+    if race.sent == Some(progress) {
+        return;
+    }
+    race.sent = Some(progress);
+
+    match &*net {
+        RaceNet::Off => {}
+        RaceNet::Host(host) => {
+            if race.record_progress(me, progress) {
+                broadcast_standings(host, &race);
+            }
+        }
+        RaceNet::Client(client) => client.send(&RaceMessage::Progress(progress)),
+    }
+}
@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Marks where the recorded player's mouse is during playback.
+#[derive(Component)]
+pub struct ReplayCursor;
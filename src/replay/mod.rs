@@ -0,0 +1,42 @@
+pub mod components;
+pub mod rawvf;
+pub mod resources;
+pub mod systems;
+
+use crate::{
+    game::{GameState, events::NewGameEvent},
+    mode::systems::start_run,
+};
+use bevy::prelude::*;
+pub use components::*;
+pub use resources::*;
+use systems::*;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayRecorder>()
+            .init_resource::<ReplayPlayback>()
+            .add_systems(Startup, spawn_replay_cursor)
+            .add_systems(
+                Update,
+                (
+                    start_recording,
+                    record_mouse
+                        .run_if(in_state(GameState::Playing))
+                        .run_if(not(replay_running)),
+                    finish_game,
+                    export_replay,
+                    play_replay.before(start_run),
+                    track_playback_start,
+                    // The replay's board only spawns at the end of the frame that deals it.
+                    run_playback
+                        .run_if(in_state(GameState::Playing))
+                        .run_if(replay_running)
+                        .run_if(not(on_event::<NewGameEvent>)),
+                )
+                    .chain(),
+            );
+    }
+}
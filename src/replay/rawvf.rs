@@ -0,0 +1,273 @@
+use crate::{
+    board::{BoardShape, layout::BoardLayout},
+    replay::{Replay, ReplayAction, ReplayEvent, ReplayResult},
+};
+use bevy::prelude::*;
+use std::fmt;
+
+/// Replay files use the RAW Minesweeper video text format (RAWVF) that competitive players'
+/// converters and analysis tools read.
+pub const EXTENSION: &str = "rawvf";
+
+// RAWVF positions are in pixels on a board of 16 pixel cells.
+const CELL_PIXELS: f32 = 16.0;
+
+#[derive(Debug)]
+pub enum RawvfError {
+    /// RAWVF only describes classic rectangular boards with one mine per cell.
+    Unsupported(&'static str),
+    MissingField(&'static str),
+    InvalidField(&'static str),
+    BoardMismatch,
+    InvalidEvent(String),
+}
+
+impl fmt::Display for RawvfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RawvfError::Unsupported(feature) => write!(f, "RAWVF cannot store {feature}"),
+            RawvfError::MissingField(field) => write!(f, "missing {field}"),
+            RawvfError::InvalidField(field) => write!(f, "invalid {field}"),
+            RawvfError::BoardMismatch => write!(f, "board does not match its size or mine count"),
+            RawvfError::InvalidEvent(line) => write!(f, "invalid event '{line}'"),
+        }
+    }
+}
+
+impl std::error::Error for RawvfError {}
+
+fn level_name(width: u8, height: u8, mines: u8) -> &'static str {
+    match (width, height, mines) {
+        (8, 8, 10) | (9, 9, 10) => "Beginner",
+        (16, 16, 40) => "Intermediate",
+        (30, 16, 99) => "Expert",
+        _ => "Custom",
+    }
+}
+
+pub fn export(replay: &Replay) -> Result<String, RawvfError> {
+    let layout = &replay.layout;
+    if replay.shape != BoardShape::Square || layout.mask.is_some() {
+        return Err(RawvfError::Unsupported("board shapes"));
+    }
+    if layout.mines.iter().any(|mines| *mines > 1) {
+        return Err(RawvfError::Unsupported("multi-mine cells"));
+    }
+
+    let mut text = String::new();
+    let mut line = |line: String| {
+        text.push_str(&line);
+        text.push('\n');
+    };
+
+    line("RawVF_Version: Rev5".to_string());
+    line("Program: Minesweeper".to_string());
+    line(format!("Version: {}", env!("CARGO_PKG_VERSION")));
+    line("Player: Anonymous".to_string());
+    line(format!("Timestamp: {}", replay.date));
+    line(format!(
+        "Level: {}",
+        level_name(layout.width, layout.height, layout.mine_count())
+    ));
+    line(format!("Width: {}", layout.width));
+    line(format!("Height: {}", layout.height));
+    line(format!("Mines: {}", layout.mine_count()));
+    line("Marks: Off".to_string());
+    line("Mode: Classic".to_string());
+
+    line("Board:".to_string());
+    for y in (0..layout.height as usize).rev() {
+        line(
+            (0..layout.width as usize)
+                .map(|x| match layout.mines[y * layout.width as usize + x] {
+                    0 => '0',
+                    _ => '*',
+                })
+                .collect(),
+        );
+    }
+
+    // Times count from the first left click, as other tools expect.
+    let start = replay
+        .events
+        .iter()
+        .find(|event| event.action == ReplayAction::LeftPress)
+        .map_or(0.0, |event| event.time);
+    let end = replay.events.last().map_or(start, |event| event.time);
+
+    line("Events:".to_string());
+    line(format!("{:.2} start", 0.0));
+    for event in &replay.events {
+        let code = match event.action {
+            ReplayAction::Move => "mv",
+            ReplayAction::LeftPress => "lc",
+            ReplayAction::LeftRelease => "lr",
+            ReplayAction::RightPress => "rc",
+            ReplayAction::RightRelease => "rr",
+        };
+        let pixels = event.cursor * CELL_PIXELS;
+        line(format!(
+            "{:.2} {code} {} {} ({} {})",
+            event.time - start,
+            event.cursor.x.floor() as i32 + 1,
+            event.cursor.y.floor() as i32 + 1,
+            pixels.x.round() as i32,
+            pixels.y.round() as i32,
+        ));
+    }
+    match replay.result {
+        Some(ReplayResult::Won) => line(format!("{:.2} won", end - start)),
+        Some(ReplayResult::Lost) => line(format!("{:.2} blast", end - start)),
+        None => {}
+    }
+
+    Ok(text)
+}
+
+pub fn import(text: &str) -> Result<Replay, RawvfError> {
+    let mut lines = text.lines().map(str::trim_end);
+    let mut header = Vec::new();
+    for line in lines.by_ref() {
+        if line.trim() == "Board:" {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            header.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let field = |name: &'static str| -> Result<u8, RawvfError> {
+        header
+            .iter()
+            .find(|(key, _)| key == name)
+            .ok_or(RawvfError::MissingField(name))?
+            .1
+            .parse()
+            .map_err(|_| RawvfError::InvalidField(name))
+    };
+    let (width, height, mines) = (field("Width")?, field("Height")?, field("Mines")?);
+    let date = header
+        .iter()
+        .find(|(key, _)| key == "Timestamp")
+        .map_or_else(String::new, |(_, value)| value.clone());
+
+    let rows: Vec<String> = lines
+        .by_ref()
+        .take_while(|line| line.trim() != "Events:")
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.trim()
+                .chars()
+                .map(|cell| if cell == '*' { '*' } else { '.' })
+                .collect()
+        })
+        .collect();
+    let layout = BoardLayout::from_rows("", &rows).map_err(|_| RawvfError::BoardMismatch)?;
+    if (layout.width, layout.height, layout.mine_count()) != (width, height, mines) {
+        return Err(RawvfError::BoardMismatch);
+    }
+
+    let mut replay = Replay::new(layout, date);
+    for line in lines.filter(|line| !line.trim().is_empty()) {
+        if let Some(event) = parse_event(line)? {
+            replay.events.push(event);
+        } else if line.contains("won") {
+            replay.result = Some(ReplayResult::Won);
+        } else if line.contains("blast") || line.contains("lost") {
+            replay.result = Some(ReplayResult::Lost);
+        }
+    }
+
+    // Playback starts when the board appears, so shift anything before the first click.
+    let first = replay.events.first().map_or(0.0, |event| event.time);
+    for event in &mut replay.events {
+        event.time -= first;
+    }
+
+    Ok(replay)
+}
+
+/// Reads a mouse event line such as `1.23 lc 4 2 (56 24)`; other events give `None`.
+fn parse_event(line: &str) -> Result<Option<ReplayEvent>, RawvfError> {
+    let invalid = || RawvfError::InvalidEvent(line.to_string());
+    let mut words = line.split_whitespace();
+    let (Some(time), Some(code)) = (words.next(), words.next()) else {
+        return Err(invalid());
+    };
+
+    let action = match code {
+        "mv" => ReplayAction::Move,
+        "lc" => ReplayAction::LeftPress,
+        "lr" => ReplayAction::LeftRelease,
+        "rc" => ReplayAction::RightPress,
+        "rr" => ReplayAction::RightRelease,
+        _ => return Ok(None),
+    };
+
+    let time: f32 = time.parse().map_err(|_| invalid())?;
+    let pixels = line
+        .split_once('(')
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .map(|(pixels, _)| pixels)
+        .ok_or_else(invalid)?;
+    let mut coordinates = pixels.split_whitespace().map(str::parse::<f32>);
+    let (Some(Ok(x)), Some(Ok(y))) = (coordinates.next(), coordinates.next()) else {
+        return Err(invalid());
+    };
+
+    Ok(Some(ReplayEvent {
+        time,
+        action,
+        cursor: Vec2::new(x, y) / CELL_PIXELS,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replay() -> Replay {
+        let rows: Vec<String> = ["*...", "..*.", "...."]
+            .iter()
+            .map(|row| row.to_string())
+            .collect();
+        let mut replay = Replay::new(
+            BoardLayout::from_rows("", &rows).unwrap(),
+            "2026-10-19 12:00".to_string(),
+        );
+        // Times on whole hundredths and cursors on whole pixels survive the text format.
+        for (time, action, x, y) in [
+            (0.0, ReplayAction::LeftPress, 1.5, 0.5),
+            (0.25, ReplayAction::LeftRelease, 1.5, 0.5),
+            (0.5, ReplayAction::Move, 2.25, 1.75),
+            (1.0, ReplayAction::RightPress, 0.5, 2.5),
+            (1.25, ReplayAction::RightRelease, 0.5, 2.5),
+        ] {
+            replay.events.push(ReplayEvent {
+                time,
+                action,
+                cursor: Vec2::new(x, y),
+            });
+        }
+        replay.result = Some(ReplayResult::Won);
+        replay
+    }
+
+    #[test]
+    fn import_reads_back_an_export() {
+        let original = replay();
+        let imported = import(&export(&original).unwrap()).unwrap();
+
+        assert_eq!(imported.layout, original.layout);
+        assert_eq!(imported.date, original.date);
+        assert_eq!(imported.events, original.events);
+        assert_eq!(imported.result, original.result);
+    }
+
+    #[test]
+    fn export_refuses_multi_mine_cells() {
+        let mut replay = replay();
+        replay.layout.mines[0] = 2;
+        assert!(matches!(export(&replay), Err(RawvfError::Unsupported(_))));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::board::{BoardShape, layout::BoardLayout, topology::TopologyKind};
+use bevy::prelude::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ReplayAction {
+    Move,
+    LeftPress,
+    LeftRelease,
+    RightPress,
+    RightRelease,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayEvent {
+    /// Seconds since the board appeared.
+    pub time: f32,
+    pub action: ReplayAction,
+    /// The mouse in board units, see [`crate::utils::world_to_board`].
+    pub cursor: Vec2,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ReplayResult {
+    Won,
+    Lost,
+}
+
+/// A recorded game: the board it was played on and every mouse move and click.
+#[derive(Clone, Debug)]
+pub struct Replay {
+    pub layout: BoardLayout,
+    pub shape: BoardShape,
+    pub topology: TopologyKind,
+    pub date: String,
+    pub events: Vec<ReplayEvent>,
+    pub result: Option<ReplayResult>,
+}
+
+impl Replay {
+    pub fn new(layout: BoardLayout, date: String) -> Self {
+        Self {
+            layout,
+            shape: BoardShape::Square,
+            topology: TopologyKind::Standard,
+            date,
+            events: Vec::new(),
+            result: None,
+        }
+    }
+}
+
+/// Records the game in progress; the last finished game stays available to export or watch.
+#[derive(Resource, Default)]
+pub struct ReplayRecorder {
+    pub recording: Option<Replay>,
+    pub finished: Option<Replay>,
+    pub clock: f32,
+}
+
+impl ReplayRecorder {
+    /// The finished game, or the one still being played.
+    pub fn latest(&self) -> Option<&Replay> {
+        self.finished.as_ref().or(self.recording.as_ref())
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ReplayPlayback {
+    pub replay: Option<Replay>,
+    pub clock: f32,
+    pub next_event: usize,
+    /// Set until the board for the replay has been dealt.
+    pub starting: bool,
+}
+
+impl ReplayPlayback {
+    pub fn start(&mut self, replay: Replay) {
+        *self = Self {
+            replay: Some(replay),
+            clock: 0.0,
+            next_event: 0,
+            starting: true,
+        };
+    }
+}
+
+/// Whether a replay is driving the board, so live input is ignored.
+pub fn replay_running(playback: Res<ReplayPlayback>) -> bool {
+    playback.replay.is_some()
+}
@@ -0,0 +1,317 @@
+use crate::{
+    board::{BoardSettings, systems::current_layout},
+    game::{GameStats, events::*},
+    input::systems::{click_tile, get_clicked_tile},
+    mode::{
+        GameMode,
+        daily::{date_string, today},
+    },
+    replay::*,
+    storage::data_dir,
+    tile::{Position, Tile, TileSprite},
+    transfer::TransferStatus,
+    utils::{board_extent, board_to_world, world_to_board},
+};
+use bevy::prelude::*;
+use std::{fs, path::Path};
+
+// Cursor moves smaller than one RAWVF pixel are not worth recording.
+const MIN_CURSOR_MOVE: f32 = 1.0 / 16.0;
+
+pub fn spawn_replay_cursor(mut commands: Commands) {
+    commands.spawn((
+        Sprite::from_color(Color::srgba(1., 1., 1., 0.9), Vec2::splat(8.0)),
+        Transform::from_translation(Vec3::new(0.0, 0.0, 5.0)),
+        Visibility::Hidden,
+        ReplayCursor,
+    ));
+}
+
+/// Starts a fresh recording whenever a new board is dealt, unless it is a replay's.
+pub fn start_recording(
+    settings: Res<BoardSettings>,
+    playback: Res<ReplayPlayback>,
+    mut recorder: ResMut<ReplayRecorder>,
+    new_tiles: Query<(), Added<TileSprite>>,
+    tile_query: Query<(&Position, &Tile), With<TileSprite>>,
+) {
+    if new_tiles.is_empty() {
+        return;
+    }
+
+    recorder.clock = 0.0;
+    recorder.recording = playback.replay.is_none().then(|| Replay {
+        shape: settings.shape,
+        topology: settings.topology,
+        ..Replay::new(
+            current_layout(&settings, tile_query.iter()),
+            date_string(today()),
+        )
+    });
+}
+
+pub fn record_mouse(
+    time: Res<Time>,
+    windows: Query<&Window>,
+    settings: Res<BoardSettings>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    ui_interactions: Query<&Interaction>,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    recorder.clock += time.delta_secs();
+    let clock = recorder.clock;
+    let Some(replay) = &mut recorder.recording else {
+        return;
+    };
+
+    let (camera, camera_transform) = *camera;
+    let Some(cursor) = windows
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .and_then(|position| camera.viewport_to_world_2d(camera_transform, position).ok())
+        .map(|world_pos| world_to_board(world_pos, &settings))
+    else {
+        return;
+    };
+
+    let extent = board_extent(&settings);
+    if cursor.x < 0.0 || cursor.y < 0.0 || cursor.x > extent.x || cursor.y > extent.y {
+        return;
+    }
+
+    // Clicks on the settings panel never reach the board, so only the cursor is recorded.
+    let ui_busy = ui_interactions
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+    let moved = replay
+        .events
+        .last()
+        .is_none_or(|event| event.cursor.distance(cursor) >= MIN_CURSOR_MOVE);
+
+    let mut record = |action| {
+        replay.events.push(ReplayEvent {
+            time: clock,
+            action,
+            cursor,
+        })
+    };
+
+    if moved {
+        record(ReplayAction::Move);
+    }
+    if ui_busy {
+        return;
+    }
+    for (button, pressed, released) in [
+        (
+            MouseButton::Left,
+            ReplayAction::LeftPress,
+            ReplayAction::LeftRelease,
+        ),
+        (
+            MouseButton::Right,
+            ReplayAction::RightPress,
+            ReplayAction::RightRelease,
+        ),
+    ] {
+        if mouse_input.just_pressed(button) {
+            record(pressed);
+        }
+        if mouse_input.just_released(button) {
+            record(released);
+        }
+    }
+}
+
+/// Keeps the game that just ended and stops any replay that was showing it.
+pub fn finish_game(
+    mut recorder: ResMut<ReplayRecorder>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut cursor: Single<&mut Visibility, With<ReplayCursor>>,
+    mut game_won_events: EventReader<GameWonEvent>,
+    mut game_over_events: EventReader<GameOverEvent>,
+) {
+    let result = match (
+        game_won_events.read().count(),
+        game_over_events.read().count(),
+    ) {
+        (0, 0) => return,
+        (0, _) => ReplayResult::Lost,
+        _ => ReplayResult::Won,
+    };
+
+    if let Some(mut replay) = recorder.recording.take() {
+        replay.result = Some(result);
+        recorder.finished = Some(replay);
+    }
+    playback.replay = None;
+    **cursor = Visibility::Hidden;
+}
+
+pub fn export_replay(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    recorder: Res<ReplayRecorder>,
+    mut status: ResMut<TransferStatus>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyV) {
+        return;
+    }
+
+    let Some(replay) = recorder.latest() else {
+        status.show("No game recorded yet".to_string());
+        return;
+    };
+
+    let dir = data_dir().join("replays");
+    let date = date_string(today());
+    let path = (1..)
+        .map(|number| dir.join(format!("replay-{date}-{number}.{}", rawvf::EXTENSION)))
+        .find(|path| !path.exists())
+        .unwrap_or_default();
+
+    let result = rawvf::export(replay)
+        .map_err(|err| err.to_string())
+        .and_then(|text| {
+            fs::create_dir_all(&dir)
+                .and_then(|_| fs::write(&path, text))
+                .map_err(|err| err.to_string())
+        });
+
+    status.show(match result {
+        Ok(()) => format!("Saved replay {}", path.display()),
+        Err(err) => format!("Could not save replay: {err}"),
+    });
+}
+
+/// Watches the last game again on `P`, or a `.rawvf` file dropped onto the window.
+#[allow(clippy::too_many_arguments)]
+pub fn play_replay(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    recorder: Res<ReplayRecorder>,
+    mut drop_events: EventReader<FileDragAndDrop>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut settings: ResMut<BoardSettings>,
+    mut game_mode: ResMut<GameMode>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+    mut status: ResMut<TransferStatus>,
+) {
+    let mut replay = None;
+
+    for event in drop_events.read() {
+        if let FileDragAndDrop::DroppedFile { path_buf, .. } = event
+            && is_replay_file(path_buf)
+        {
+            match fs::read_to_string(path_buf)
+                .map_err(|err| err.to_string())
+                .and_then(|text| rawvf::import(&text).map_err(|err| err.to_string()))
+            {
+                Ok(loaded) => replay = Some(loaded),
+                Err(err) => status.show(format!("Could not load replay: {err}")),
+            }
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::KeyP) {
+        match &recorder.finished {
+            Some(finished) => replay = Some(finished.clone()),
+            None => status.show("No finished game to replay".to_string()),
+        }
+    }
+
+    let Some(replay) = replay else {
+        return;
+    };
+
+    settings.apply_layout(replay.layout.clone());
+    settings.shape = replay.shape;
+    settings.topology = replay.topology;
+    game_mode.set_if_neq(GameMode::Classic);
+    new_game_events.write(NewGameEvent);
+    status.show("Playing replay".to_string());
+    playback.start(replay);
+}
+
+pub fn is_replay_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == rawvf::EXTENSION)
+}
+
+/// Lets the replay's own new game through and stops the replay on any other.
+pub fn track_playback_start(
+    mut playback: ResMut<ReplayPlayback>,
+    mut cursor: Single<&mut Visibility, With<ReplayCursor>>,
+    mut new_game_events: EventReader<NewGameEvent>,
+) {
+    for _ in new_game_events.read() {
+        if playback.starting {
+            playback.starting = false;
+        } else if playback.replay.is_some() {
+            playback.replay = None;
+            **cursor = Visibility::Hidden;
+        }
+    }
+}
+
+/// Moves the replay cursor and repeats the recorded clicks once their time comes.
+#[allow(clippy::too_many_arguments)]
+pub fn run_playback(
+    time: Res<Time>,
+    settings: Res<BoardSettings>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut cursor: Single<(&mut Transform, &mut Visibility), With<ReplayCursor>>,
+    tile_query: Query<(&Position, &Tile), With<TileSprite>>,
+    mut game_stats: ResMut<GameStats>,
+    mut tile_flagged_events: EventWriter<TileFlaggedEvent>,
+    mut tile_revealed_events: EventWriter<TileRevealedEvent>,
+    mut chord_events: EventWriter<ChordEvent>,
+) {
+    if playback.starting {
+        return;
+    }
+
+    let playback = &mut *playback;
+    let Some(replay) = &playback.replay else {
+        return;
+    };
+    playback.clock += time.delta_secs();
+
+    let (transform, visibility) = &mut *cursor;
+    **visibility = Visibility::Visible;
+
+    while let Some(event) = replay.events.get(playback.next_event)
+        && event.time <= playback.clock
+    {
+        playback.next_event += 1;
+        let world_pos = board_to_world(event.cursor, &settings);
+        transform.translation.x = world_pos.x;
+        transform.translation.y = world_pos.y;
+
+        let left_click = match event.action {
+            ReplayAction::LeftPress => true,
+            ReplayAction::RightPress => false,
+            _ => continue,
+        };
+        if let Some((position, tile)) = get_clicked_tile(world_pos, &settings, &tile_query) {
+            click_tile(
+                position,
+                &tile,
+                left_click,
+                &tile_query,
+                &settings,
+                &mut game_stats,
+                &mut tile_flagged_events,
+                &mut tile_revealed_events,
+                &mut chord_events,
+            );
+        }
+        // The board only reflects a click next frame, and a chord depends on what it shows.
+        break;
+    }
+
+    if playback.next_event >= replay.events.len() {
+        playback.replay = None;
+        **visibility = Visibility::Hidden;
+    }
+}
@@ -0,0 +1,316 @@
+//! The rules of play on plain tiles, shared by the window's systems and the [`Engine`] so both
+//! open, flag, chord and end games the same way.
+//!
+//! [`Engine`]: crate::engine::Engine
+
+use crate::{
+    board::layout::BoardLayout,
+    solver::{Cell, SolverBoard},
+    tile::Tile,
+};
+
+/// What opening a cell did.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Reveal {
+    /// The cell is missing, already open or flagged.
+    Nothing,
+    /// A safe cell, spreading through any blank area around it.
+    Safe,
+    /// A mine flagged in place, so play carries on.
+    Claimed,
+    /// A mine that ends the game.
+    Exploded,
+}
+
+/// The tiles of a board by tile index, with `None` where a mask leaves a hole.
+#[derive(Clone, Debug)]
+pub struct Minefield {
+    pub tiles: Vec<Option<Tile>>,
+    pub neighbours: Vec<Vec<usize>>,
+    pub max_mines_per_cell: u8,
+    /// Mines opened are flagged in place and scored rather than ending the game, as in rush and
+    /// versus.
+    pub claims_mines: bool,
+    /// Mines the player may still open, counting the one that ends the game; 0 and 1 both mean
+    /// the first mine does.
+    pub lives: u8,
+}
+
+impl Minefield {
+    /// `layout`'s cells as tiles, with the classic rules where the first mine ends the game.
+    pub fn from_layout(
+        layout: &BoardLayout,
+        neighbours: Vec<Vec<usize>>,
+        max_mines_per_cell: u8,
+    ) -> Self {
+        let tiles = (0..layout.mines.len())
+            .map(|cell| {
+                layout.has_cell(cell).then(|| {
+                    let mines = layout.mines[cell];
+                    let number = neighbours[cell]
+                        .iter()
+                        .map(|neighbour| layout.mines[*neighbour])
+                        .sum();
+                    Tile {
+                        mines,
+                        flags: layout.flags[cell],
+                        is_revealed: layout.revealed[cell],
+                        is_exploded: false,
+                        adjacent_mines: if mines > 0 { 0 } else { number },
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            tiles,
+            neighbours,
+            max_mines_per_cell,
+            claims_mines: false,
+            lives: 0,
+        }
+    }
+
+    pub fn tile(&self, cell: usize) -> Option<&Tile> {
+        self.tiles.get(cell)?.as_ref()
+    }
+
+    pub fn is_hidden(&self, cell: usize) -> bool {
+        self.tile(cell).is_some_and(|tile| !tile.is_revealed)
+    }
+
+    /// What a player sees, with flags taken at their word and any mine opened known for one.
+    pub fn visible(&self) -> SolverBoard {
+        SolverBoard {
+            cells: self
+                .tiles
+                .iter()
+                .map(|tile| match tile {
+                    None => Cell::Missing,
+                    Some(tile) if tile.is_revealed && tile.is_mine() => Cell::Mine(tile.mines),
+                    Some(tile) if tile.is_flagged() => Cell::Mine(tile.flags),
+                    Some(tile) if tile.is_revealed => Cell::Revealed(tile.adjacent_mines),
+                    Some(_) => Cell::Hidden,
+                })
+                .collect(),
+            neighbours: self.neighbours.clone(),
+            max_mines_per_cell: self.max_mines_per_cell,
+            total_mines: self
+                .tiles
+                .iter()
+                .flatten()
+                .map(|tile| tile.mines as u32)
+                .sum(),
+        }
+    }
+
+    /// Mines not yet flagged, below zero once there are more flags than mines.
+    pub fn mines_left(&self) -> i16 {
+        self.tiles
+            .iter()
+            .flatten()
+            .map(|tile| tile.mines as i16 - tile.flags as i16)
+            .sum()
+    }
+
+    pub fn adjacent_flags(&self, cell: usize) -> u8 {
+        self.neighbours[cell]
+            .iter()
+            .filter_map(|neighbour| self.tile(*neighbour))
+            .map(|tile| tile.flags)
+            .sum()
+    }
+
+    /// Opens `cell` if it is hidden and unflagged, spreading from blanks. A mine is claimed when
+    /// mines score or a spare life covers it, and otherwise ends the game.
+    pub fn reveal(&mut self, cell: usize) -> Reveal {
+        let Some(tile) = self.tiles.get_mut(cell).and_then(Option::as_mut) else {
+            return Reveal::Nothing;
+        };
+        if tile.is_revealed || tile.is_flagged() {
+            return Reveal::Nothing;
+        }
+
+        if tile.is_mine() {
+            tile.explode();
+            if self.claims_mines || self.lives > 1 {
+                if !self.claims_mines {
+                    self.lives -= 1;
+                }
+                tile.flags = tile.mines;
+                return Reveal::Claimed;
+            }
+            self.lives = 0;
+            return Reveal::Exploded;
+        }
+
+        tile.reveal();
+        let mut to_open = vec![cell];
+        while let Some(cell) = to_open.pop() {
+            if self.tile(cell).is_none_or(|tile| tile.adjacent_mines > 0) {
+                continue;
+            }
+            for neighbour in self.neighbours[cell].clone() {
+                if let Some(tile) = self.tiles[neighbour].as_mut()
+                    && !tile.is_revealed
+                    && !tile.is_flagged()
+                    && !tile.is_mine()
+                {
+                    tile.reveal();
+                    to_open.push(neighbour);
+                }
+            }
+        }
+        Reveal::Safe
+    }
+
+    /// Cycles the flags on a hidden cell; returns whether it had one to cycle.
+    pub fn flag(&mut self, cell: usize) -> bool {
+        match self.tiles.get_mut(cell).and_then(Option::as_mut) {
+            Some(tile) if !tile.is_revealed => {
+                tile.cycle_flag(self.max_mines_per_cell);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// An opened number can be chorded once as many flags surround it as it shows.
+    pub fn can_chord(&self, cell: usize) -> bool {
+        self.tile(cell).is_some_and(|tile| {
+            tile.is_revealed && !tile.is_mine() && self.adjacent_flags(cell) == tile.adjacent_mines
+        })
+    }
+
+    /// The cells a chord on `cell` opens: its hidden, unflagged neighbours, or none if it cannot
+    /// be chorded.
+    pub fn chord_targets(&self, cell: usize) -> Vec<usize> {
+        if !self.can_chord(cell) {
+            return Vec::new();
+        }
+        self.neighbours[cell]
+            .iter()
+            .copied()
+            .filter(|neighbour| {
+                self.tile(*neighbour)
+                    .is_some_and(|tile| !tile.is_revealed && !tile.is_flagged())
+            })
+            .collect()
+    }
+
+    pub fn is_cleared(&self) -> bool {
+        is_cleared(self.tiles.iter().flatten())
+    }
+
+    /// Shows every mine and wrong flag, as the game over screen does.
+    pub fn lose(&mut self) {
+        for tile in self.tiles.iter_mut().flatten() {
+            if tile.mines != tile.flags {
+                tile.reveal();
+            }
+        }
+    }
+
+    /// Flags every mine left, as a cleared board does.
+    pub fn win(&mut self) {
+        for tile in self.tiles.iter_mut().flatten() {
+            tile.flags = tile.mines;
+        }
+    }
+}
+
+/// Whether every safe cell is open. Cells can hold several mines, so safe cells are counted
+/// rather than the mine total subtracted.
+pub fn is_cleared<'a>(tiles: impl IntoIterator<Item = &'a Tile>) -> bool {
+    let mut safe = 0;
+    for tile in tiles {
+        if !tile.is_mine() {
+            if !tile.is_revealed {
+                return false;
+            }
+            safe += 1;
+        }
+    }
+    safe > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{BoardSettings, grid::neighbour_table};
+
+    /// A single row of cells in the layout format, with the classic rules.
+    fn field(row: &str) -> Minefield {
+        let layout = BoardLayout::from_rows("test", &[row.to_string()]).unwrap();
+        let settings = BoardSettings {
+            width: layout.width,
+            height: layout.height,
+            ..Default::default()
+        };
+        Minefield::from_layout(&layout, neighbour_table(&settings), 1)
+    }
+
+    fn revealed(field: &Minefield) -> Vec<bool> {
+        field
+            .tiles
+            .iter()
+            .flatten()
+            .map(|tile| tile.is_revealed)
+            .collect()
+    }
+
+    #[test]
+    fn a_cascade_stops_at_the_numbers() {
+        let mut field = field("...*.");
+        assert_eq!(field.reveal(0), Reveal::Safe);
+        assert_eq!(revealed(&field), [true, true, true, false, false]);
+        assert!(!field.is_cleared());
+    }
+
+    #[test]
+    fn a_chord_trusts_wrong_flags() {
+        let mut wrong = field("*..");
+        wrong.reveal(1);
+        assert!(wrong.flag(2));
+        assert!(wrong.can_chord(1));
+        assert_eq!(wrong.chord_targets(1), [0]);
+        assert_eq!(wrong.reveal(0), Reveal::Exploded);
+
+        // One flag too many and the number cannot be chorded at all.
+        let mut crowded = field("*..");
+        crowded.reveal(1);
+        crowded.flag(0);
+        crowded.flag(2);
+        assert!(!crowded.can_chord(1));
+        assert!(crowded.chord_targets(1).is_empty());
+    }
+
+    #[test]
+    fn a_spare_life_covers_a_mine_until_the_last() {
+        let mut field = field("*.*.");
+        field.lives = 2;
+        assert_eq!(field.reveal(0), Reveal::Claimed);
+        assert_eq!(field.lives, 1);
+        assert_eq!(field.tile(0).map(|tile| tile.flags), Some(1));
+        assert_eq!(field.reveal(2), Reveal::Exploded);
+        assert_eq!(field.lives, 0);
+    }
+
+    #[test]
+    fn without_lives_the_first_mine_ends_the_game() {
+        let mut field = field(".*");
+        assert_eq!(field.reveal(1), Reveal::Exploded);
+        assert!(field.tile(1).is_some_and(|tile| tile.is_exploded));
+    }
+
+    #[test]
+    fn rush_claims_every_mine() {
+        let mut field = field("*.*.");
+        field.claims_mines = true;
+        assert_eq!(field.reveal(0), Reveal::Claimed);
+        assert_eq!(field.reveal(2), Reveal::Claimed);
+        assert_eq!(field.lives, 0);
+        assert_eq!(field.mines_left(), 0);
+        assert_eq!(field.reveal(0), Reveal::Nothing);
+    }
+}
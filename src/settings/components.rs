@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct SettingsPanel;
+
+#[derive(Component, Copy, Clone, Debug, PartialEq)]
+pub enum SettingControl {
+    MasterVolume,
+    EffectsVolume,
+    Mute,
+    Animations,
+    BoardShape,
+    Topology,
+    Mask,
+    MultiMine,
+    Lives,
+    Mode,
+    AutoplaySpeed,
+    AutoplayGuess,
+}
+
+#[derive(Component)]
+pub struct SliderFill(pub SettingControl);
+
+#[derive(Component)]
+pub struct SettingLabel(pub SettingControl);
@@ -0,0 +1,25 @@
+pub mod components;
+pub mod systems;
+
+use crate::net::playing_online;
+use bevy::prelude::*;
+pub use components::*;
+use systems::*;
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_settings_panel).add_systems(
+            Update,
+            (
+                toggle_settings_panel,
+                handle_slider_input,
+                handle_setting_buttons,
+                handle_game_buttons.run_if(not(playing_online)),
+                update_setting_widgets,
+            )
+                .chain(),
+        );
+    }
+}
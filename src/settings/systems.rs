@@ -0,0 +1,327 @@
+use crate::{
+    animation::AnimationSettings,
+    autoplay::AutoplaySettings,
+    board::{BoardSettings, BoardShape, MaskLibrary},
+    game::{LivesSettings, events::NewGameEvent},
+    mode::GameMode,
+    settings::{SettingControl, SettingLabel, SettingsPanel, SliderFill},
+    sound::AudioSettings,
+};
+use bevy::{ecs::system::SystemParam, prelude::*, ui::RelativeCursorPosition};
+
+const PANEL_FONT_SIZE: f32 = 14.0;
+const SLIDER_WIDTH: f32 = 120.0;
+
+pub fn spawn_settings_panel(mut commands: Commands, audio_settings: Res<AudioSettings>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0., 0., 0., 0.85)),
+            Interaction::default(),
+            Visibility::Hidden,
+            SettingsPanel,
+        ))
+        .with_children(|panel| {
+            spawn_slider_row(
+                panel,
+                SettingControl::MasterVolume,
+                audio_settings.master_volume,
+            );
+            spawn_slider_row(
+                panel,
+                SettingControl::EffectsVolume,
+                audio_settings.effects_volume,
+            );
+            spawn_button_row(panel, SettingControl::Mute);
+            spawn_button_row(panel, SettingControl::Animations);
+            spawn_button_row(panel, SettingControl::BoardShape);
+            spawn_button_row(panel, SettingControl::Topology);
+            spawn_button_row(panel, SettingControl::Mask);
+            spawn_button_row(panel, SettingControl::MultiMine);
+            spawn_button_row(panel, SettingControl::Lives);
+            spawn_button_row(panel, SettingControl::Mode);
+            spawn_button_row(panel, SettingControl::AutoplaySpeed);
+            spawn_button_row(panel, SettingControl::AutoplayGuess);
+        });
+}
+
+fn spawn_slider_row(panel: &mut ChildSpawnerCommands, control: SettingControl, value: f32) {
+    panel.spawn(row_node()).with_children(|row| {
+        row.spawn((
+            Text::default(),
+            TextFont {
+                font_size: PANEL_FONT_SIZE,
+                ..default()
+            },
+            Node {
+                width: Val::Px(110.0),
+                ..default()
+            },
+            SettingLabel(control),
+        ));
+        row.spawn((
+            Node {
+                width: Val::Px(SLIDER_WIDTH),
+                height: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+            Interaction::default(),
+            RelativeCursorPosition::default(),
+            control,
+        ))
+        .with_child((
+            Node {
+                width: Val::Percent(value * 100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(Color::WHITE),
+            SliderFill(control),
+        ));
+    });
+}
+
+fn spawn_button_row(panel: &mut ChildSpawnerCommands, control: SettingControl) {
+    panel.spawn(row_node()).with_children(|row| {
+        row.spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+            control,
+        ))
+        .with_child((
+            Text::default(),
+            TextFont {
+                font_size: PANEL_FONT_SIZE,
+                ..default()
+            },
+            SettingLabel(control),
+        ));
+    });
+}
+
+fn row_node() -> Node {
+    Node {
+        flex_direction: FlexDirection::Row,
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(10.0),
+        ..default()
+    }
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled { "On" } else { "Off" }
+}
+
+#[derive(SystemParam)]
+pub struct SettingValues<'w> {
+    audio: Res<'w, AudioSettings>,
+    animation: Res<'w, AnimationSettings>,
+    autoplay: Res<'w, AutoplaySettings>,
+    board: Res<'w, BoardSettings>,
+    lives: Res<'w, LivesSettings>,
+    mode: Res<'w, GameMode>,
+}
+
+impl SettingValues<'_> {
+    fn is_changed(&self) -> bool {
+        self.audio.is_changed()
+            || self.animation.is_changed()
+            || self.autoplay.is_changed()
+            || self.board.is_changed()
+            || self.lives.is_changed()
+            || self.mode.is_changed()
+    }
+
+    fn label(&self, control: SettingControl) -> String {
+        match control {
+            SettingControl::MasterVolume => {
+                format!("Master {:.0}%", self.audio.master_volume * 100.0)
+            }
+            SettingControl::EffectsVolume => {
+                format!("Effects {:.0}%", self.audio.effects_volume * 100.0)
+            }
+            SettingControl::Mute => format!("Sound: {}", on_off(!self.audio.muted)),
+            SettingControl::Animations => {
+                format!("Animations: {}", on_off(self.animation.enabled))
+            }
+            SettingControl::BoardShape => format!("Board: {}", self.board.shape.name()),
+            SettingControl::Topology => match self.board.shape {
+                BoardShape::Square => format!("Neighbours: {}", self.board.topology.name()),
+                BoardShape::Hex => "Neighbours: Hex".to_string(),
+                BoardShape::Triangle => "Neighbours: Corners".to_string(),
+            },
+            SettingControl::Mask => match &self.board.mask {
+                Some(mask) => format!("Mask: {}", mask.name),
+                None => "Mask: None".to_string(),
+            },
+            SettingControl::MultiMine => {
+                format!("Mines per cell: 1-{}", self.board.max_mines_per_cell())
+            }
+            SettingControl::Lives => match self.lives.lives {
+                0 => "Lives: Off".to_string(),
+                lives => format!("Lives: {lives}"),
+            },
+            SettingControl::Mode => format!("Mode: {}", self.mode.name()),
+            SettingControl::AutoplaySpeed => {
+                format!("Autoplay: {} moves/s", self.autoplay.moves_per_second)
+            }
+            SettingControl::AutoplayGuess => {
+                format!("Autoplay guesses: {}", on_off(self.autoplay.guess))
+            }
+        }
+    }
+}
+
+pub fn toggle_settings_panel(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut panel: Single<&mut Visibility, With<SettingsPanel>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        **panel = match **panel {
+            Visibility::Hidden => Visibility::Visible,
+            _ => Visibility::Hidden,
+        };
+    }
+}
+
+pub fn handle_slider_input(
+    mut audio_settings: ResMut<AudioSettings>,
+    slider_query: Query<(&Interaction, &RelativeCursorPosition, &SettingControl), Without<Button>>,
+) {
+    for (interaction, cursor, control) in slider_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(normalized) = cursor.normalized else {
+            continue;
+        };
+
+        let value = normalized.x.clamp(0.0, 1.0);
+        let mut updated = audio_settings.clone();
+        match control {
+            SettingControl::MasterVolume => updated.master_volume = value,
+            SettingControl::EffectsVolume => updated.effects_volume = value,
+            _ => continue,
+        }
+        audio_settings.set_if_neq(updated);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn handle_setting_buttons(
+    mut audio_settings: ResMut<AudioSettings>,
+    mut animation_settings: ResMut<AnimationSettings>,
+    mut autoplay_settings: ResMut<AutoplaySettings>,
+    button_query: Query<(&Interaction, &SettingControl), (Changed<Interaction>, With<Button>)>,
+) {
+    for (interaction, control) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match control {
+            SettingControl::Mute => audio_settings.muted = !audio_settings.muted,
+            SettingControl::Animations => {
+                animation_settings.enabled = !animation_settings.enabled;
+            }
+            SettingControl::AutoplaySpeed => {
+                autoplay_settings.moves_per_second = autoplay_settings.next_speed();
+            }
+            SettingControl::AutoplayGuess => autoplay_settings.guess = !autoplay_settings.guess,
+            _ => {}
+        }
+    }
+}
+
+/// The buttons that change the game itself; an online game keeps the board, lives and mode
+/// everyone agreed on, so these only run offline.
+#[allow(clippy::type_complexity)]
+pub fn handle_game_buttons(
+    mut board_settings: ResMut<BoardSettings>,
+    mut lives_settings: ResMut<LivesSettings>,
+    mut game_mode: ResMut<GameMode>,
+    mask_library: Res<MaskLibrary>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+    button_query: Query<(&Interaction, &SettingControl), (Changed<Interaction>, With<Button>)>,
+) {
+    for (interaction, control) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match control {
+            SettingControl::BoardShape => {
+                board_settings.shape = board_settings.shape.next();
+                board_settings.layout = None;
+                new_game_events.write(NewGameEvent);
+            }
+            SettingControl::Topology if board_settings.shape == BoardShape::Square => {
+                board_settings.topology = board_settings.topology.next();
+                new_game_events.write(NewGameEvent);
+            }
+            SettingControl::Mask => {
+                let mask = mask_library.next(board_settings.mask.as_ref());
+                board_settings.set_mask(mask);
+                new_game_events.write(NewGameEvent);
+            }
+            SettingControl::MultiMine => {
+                board_settings.multi_mine = !board_settings.multi_mine;
+                board_settings.layout = None;
+                new_game_events.write(NewGameEvent);
+            }
+            SettingControl::Lives => {
+                lives_settings.lives = lives_settings.next();
+                new_game_events.write(NewGameEvent);
+            }
+            SettingControl::Mode => {
+                *game_mode = game_mode.next();
+                // A fixed board belongs to the mode it was opened in.
+                if board_settings.layout.is_some() {
+                    *board_settings = BoardSettings {
+                        tile_size: board_settings.tile_size,
+                        ..default()
+                    };
+                }
+                new_game_events.write(NewGameEvent);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn update_setting_widgets(
+    values: SettingValues,
+    new_labels: Query<(), Added<SettingLabel>>,
+    mut label_query: Query<(&SettingLabel, &mut Text)>,
+    mut fill_query: Query<(&SliderFill, &mut Node)>,
+) {
+    if !values.is_changed() && new_labels.is_empty() {
+        return;
+    }
+
+    for (label, mut text) in label_query.iter_mut() {
+        text.0 = values.label(label.0);
+    }
+
+    for (fill, mut node) in fill_query.iter_mut() {
+        let value = match fill.0 {
+            SettingControl::MasterVolume => values.audio.master_volume,
+            SettingControl::EffectsVolume => values.audio.effects_volume,
+            _ => continue,
+        };
+        node.width = Val::Percent(value * 100.0);
+    }
+}
@@ -0,0 +1,782 @@
+use crate::board::layout::BoardLayout;
+use std::collections::{BTreeMap, BTreeSet};
+
+// Larger frontier groups are skipped rather than enumerated.
+const MAX_GROUP_SIZE: usize = 48;
+const MAX_SEARCH_STEPS: usize = 200_000;
+const GUESS_SEED_MULTIPLIER: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// What the player can see of a cell.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Cell {
+    Missing,
+    Hidden,
+    /// A hidden cell known to hold this many mines.
+    Mine(u8),
+    /// An opened safe cell showing its number.
+    Revealed(u8),
+}
+
+/// The visible state of a board, with cell neighbours given by index so any topology or mask
+/// works.
+#[derive(Clone, Debug)]
+pub struct SolverBoard {
+    pub cells: Vec<Cell>,
+    pub neighbours: Vec<Vec<usize>>,
+    pub max_mines_per_cell: u8,
+    pub total_mines: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Deductions {
+    pub safe: Vec<usize>,
+    pub mines: Vec<(usize, u8)>,
+}
+
+impl Deductions {
+    pub fn is_empty(&self) -> bool {
+        self.safe.is_empty() && self.mines.is_empty()
+    }
+}
+
+/// How far pure logic gets on a board from its starting reveals.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolveReport {
+    pub solved: bool,
+    pub safe_cells: usize,
+    pub revealed_cells: usize,
+}
+
+/// What a group's assignments holding the same number of mines have in common.
+#[derive(Clone, Debug)]
+struct Tally {
+    /// Each variable's lowest and highest mine count.
+    lowest: Vec<u8>,
+    highest: Vec<u8>,
+    /// How many assignments there are, and how many of them put a mine in each variable.
+    count: f64,
+    mined: Vec<f64>,
+}
+
+/// Per total mine count of a group, what its assignments with that many mines have in common.
+type Outcomes = BTreeMap<u32, Tally>;
+
+/// The groups searched, with their variables and outcomes, and those given up on.
+type Groups = (Vec<(Vec<usize>, Outcomes)>, Vec<Vec<usize>>);
+
+struct Constraint {
+    cells: Vec<usize>,
+    mines: u32,
+}
+
+impl SolverBoard {
+    /// Every hidden cell whose contents follow from the numbers and the mine count.
+    pub fn deduce(&self) -> Deductions {
+        let constraints = self.constraints();
+        let (groups, unsearched) = self.search_groups(&constraints);
+        let complete = unsearched.is_empty();
+
+        let frontier: Vec<usize> = constraints
+            .iter()
+            .flat_map(|constraint| constraint.cells.iter().copied())
+            .collect();
+        let rest: Vec<usize> = (0..self.cells.len())
+            .filter(|index| self.cells[*index] == Cell::Hidden && !frontier.contains(index))
+            .collect();
+        let mines_left = self.total_mines.saturating_sub(self.known_mines());
+        let rest_capacity = rest.len() as u32 * self.max_mines_per_cell as u32;
+        // With a group left unsearched its mine count is unknown, so the total proves nothing.
+        let fits = |frontier_mines: u32| {
+            !complete
+                || (frontier_mines <= mines_left && mines_left - frontier_mines <= rest_capacity)
+        };
+
+        let mut deductions = Deductions::default();
+        for (group, (variables, outcomes)) in groups.iter().enumerate() {
+            let others = mine_totals(
+                groups
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(other, entry)| (other != group).then_some(&entry.1)),
+            );
+
+            let mut lowest = vec![u8::MAX; variables.len()];
+            let mut highest = vec![0; variables.len()];
+            for (mines, tally) in outcomes {
+                if others.iter().any(|other| fits(mines + other)) {
+                    for index in 0..variables.len() {
+                        lowest[index] = lowest[index].min(tally.lowest[index]);
+                        highest[index] = highest[index].max(tally.highest[index]);
+                    }
+                }
+            }
+
+            for (index, cell) in variables.iter().enumerate() {
+                if highest[index] == 0 {
+                    deductions.safe.push(*cell);
+                } else if lowest[index] == highest[index] {
+                    deductions.mines.push((*cell, lowest[index]));
+                }
+            }
+        }
+
+        if complete && !rest.is_empty() {
+            let rest_mines: Vec<u32> = mine_totals(groups.iter().map(|entry| &entry.1))
+                .into_iter()
+                .filter(|mines| fits(*mines))
+                .map(|mines| mines_left - mines)
+                .collect();
+
+            if rest_mines.iter().all(|mines| *mines == 0) {
+                deductions.safe.extend(&rest);
+            } else if rest_mines.iter().all(|mines| *mines == rest_capacity) {
+                deductions
+                    .mines
+                    .extend(rest.iter().map(|cell| (*cell, self.max_mines_per_cell)));
+            }
+        }
+
+        deductions
+    }
+
+    /// The hidden cell least likely to hold a mine, for when nothing is proven.
+    pub fn safest_guess(&self) -> Option<usize> {
+        let chances = self.mine_chances();
+        (0..self.cells.len())
+            .filter(|cell| self.cells[*cell] == Cell::Hidden)
+            .min_by(|a, b| chances[*a].total_cmp(&chances[*b]))
+    }
+
+    /// Each hidden cell's chance of holding a mine, counting every placement of the mines left
+    /// that fits the numbers as equally likely. A group too large to search takes an unknown
+    /// share of the mines, so its cells and those away from the numbers are rated by density.
+    fn mine_chances(&self) -> Vec<f64> {
+        let constraints = self.constraints();
+        let (groups, unsearched) = self.search_groups(&constraints);
+        let max = self.max_mines_per_cell;
+        let mut chances = vec![0.0; self.cells.len()];
+
+        let frontier: Vec<usize> = constraints
+            .iter()
+            .flat_map(|constraint| constraint.cells.iter().copied())
+            .collect();
+        let rest: Vec<usize> = (0..self.cells.len())
+            .filter(|index| self.cells[*index] == Cell::Hidden && !frontier.contains(index))
+            .collect();
+        let mines_left = self.total_mines.saturating_sub(self.known_mines());
+        let (fewer, all) = arrangements(rest.len(), mines_left, max);
+        let complete = unsearched.is_empty();
+        // Ways to place the mines the frontier leaves over in the rest.
+        let weight = |frontier_mines: u32| match mines_left.checked_sub(frontier_mines) {
+            _ if !complete => 1.0,
+            Some(left) => all.get(left as usize).copied().unwrap_or(0.0),
+            None => 0.0,
+        };
+
+        // Counts are scaled to each group's most common total; only their ratios matter.
+        let scales: Vec<f64> = groups
+            .iter()
+            .map(|(_, outcomes)| {
+                outcomes
+                    .values()
+                    .map(|tally| tally.count)
+                    .fold(0.0, f64::max)
+            })
+            .collect();
+        let totals: Vec<BTreeMap<u32, f64>> = groups
+            .iter()
+            .zip(&scales)
+            .map(|((_, outcomes), scale)| {
+                outcomes
+                    .iter()
+                    .map(|(mines, tally)| (*mines, tally.count / scale))
+                    .collect()
+            })
+            .collect();
+        let combined = combine_totals(totals.iter());
+        let placements: f64 = combined
+            .iter()
+            .map(|(mines, count)| count * weight(*mines))
+            .sum();
+        if placements <= 0.0 {
+            return chances;
+        }
+
+        for (group, (variables, outcomes)) in groups.iter().enumerate() {
+            let others = combine_totals(
+                totals
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(other, entry)| (other != group).then_some(entry)),
+            );
+            for (mines, tally) in outcomes {
+                let share = others
+                    .iter()
+                    .map(|(other, count)| count * weight(mines + other))
+                    .sum::<f64>()
+                    / (scales[group] * placements);
+                for (index, cell) in variables.iter().enumerate() {
+                    chances[*cell] += tally.mined[index] * share;
+                }
+            }
+        }
+
+        if complete {
+            // A cell of the rest is empty in the placements that leave it out.
+            let chance = combined
+                .iter()
+                .filter_map(|(mines, count)| {
+                    let left = mines_left.checked_sub(*mines)? as usize;
+                    Some(count * (all.get(left)? - fewer.get(left)?))
+                })
+                .sum::<f64>()
+                / placements;
+            for cell in &rest {
+                chances[*cell] = chance;
+            }
+        } else {
+            let capacity = |cells: usize| (cells * max as usize).max(1) as f64;
+            for index in unsearched.iter().flatten() {
+                let constraint = &constraints[*index];
+                let chance = constraint.mines as f64 / capacity(constraint.cells.len());
+                for cell in &constraint.cells {
+                    chances[*cell] = chances[*cell].max(chance);
+                }
+            }
+
+            let mut frontier = frontier;
+            frontier.sort_unstable();
+            frontier.dedup();
+            let frontier_mines: f64 = frontier
+                .iter()
+                .map(|cell| chances[*cell] * max as f64)
+                .sum();
+            let chance = (mines_left as f64 - frontier_mines).max(0.0) / capacity(rest.len());
+            for cell in &rest {
+                chances[*cell] = chance;
+            }
+        }
+
+        chances
+    }
+
+    fn known_mines(&self) -> u32 {
+        self.cells
+            .iter()
+            .map(|cell| match cell {
+                Cell::Mine(mines) => *mines as u32,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    fn constraints(&self) -> Vec<Constraint> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell)| {
+                let Cell::Revealed(number) = cell else {
+                    return None;
+                };
+
+                let mut known = 0;
+                let mut cells = Vec::new();
+                for neighbour in &self.neighbours[index] {
+                    match self.cells[*neighbour] {
+                        Cell::Hidden => cells.push(*neighbour),
+                        Cell::Mine(mines) => known += mines as u32,
+                        _ => {}
+                    }
+                }
+
+                (!cells.is_empty()).then(|| Constraint {
+                    cells,
+                    mines: (*number as u32).saturating_sub(known),
+                })
+            })
+            .collect()
+    }
+
+    /// Splits the numbers into groups sharing hidden cells and searches each, giving up on
+    /// groups too large to search.
+    fn search_groups(&self, constraints: &[Constraint]) -> Groups {
+        let mut searched = Vec::new();
+        let mut unsearched = Vec::new();
+        for group in group_constraints(constraints) {
+            let variables = group_variables(constraints, &group);
+            let outcomes = (variables.len() <= MAX_GROUP_SIZE)
+                .then(|| self.enumerate(constraints, &group, &variables))
+                .flatten();
+            match outcomes {
+                Some(outcomes) => searched.push((variables, outcomes)),
+                None => unsearched.push(group),
+            }
+        }
+        (searched, unsearched)
+    }
+
+    /// For each number of mines the group can hold, a tally of the matching assignments; `None`
+    /// if there are none or the search gives up.
+    fn enumerate(
+        &self,
+        constraints: &[Constraint],
+        group: &[usize],
+        variables: &[usize],
+    ) -> Option<Outcomes> {
+        let mut search = Search {
+            targets: group
+                .iter()
+                .map(|index| constraints[*index].mines)
+                .collect(),
+            sums: vec![0; group.len()],
+            open: group
+                .iter()
+                .map(|index| constraints[*index].cells.len() as u32)
+                .collect(),
+            variable_constraints: variables
+                .iter()
+                .map(|cell| {
+                    (0..group.len())
+                        .filter(|position| constraints[group[*position]].cells.contains(cell))
+                        .collect()
+                })
+                .collect(),
+            max: self.max_mines_per_cell,
+            values: vec![0; variables.len()],
+            outcomes: Outcomes::new(),
+            steps: 0,
+        };
+
+        search.assign(0);
+        (!search.outcomes.is_empty() && search.steps <= MAX_SEARCH_STEPS).then_some(search.outcomes)
+    }
+}
+
+/// Plays a board from its deductions one move at a time, as autoplay and the solver bot do.
+#[derive(Clone, Debug, Default)]
+pub struct Planner {
+    /// Cells from the last deductions still to play, with their mines; 0 to open.
+    pub planned: Vec<(usize, u8)>,
+}
+
+impl Planner {
+    /// The next proven move on `board`: a cell to open when its mines are 0, or else to flag
+    /// once more. `None` when nothing is proven and the next move would be a guess.
+    pub fn next_move(&mut self, board: &SolverBoard) -> Option<(usize, u8)> {
+        let pending = |(cell, mines): &(usize, u8)| match board.cells[*cell] {
+            Cell::Hidden => true,
+            Cell::Mine(flags) => flags < *mines,
+            _ => false,
+        };
+        self.planned.retain(pending);
+        if self.planned.is_empty() {
+            // Everything one pass finds is played before solving again, as opening several cells
+            // at once keeps the frontier groups small enough to search.
+            let deductions = board.deduce();
+            self.planned.extend(deductions.mines);
+            self.planned
+                .extend(deductions.safe.iter().map(|cell| (*cell, 0)));
+            self.planned.retain(pending);
+            self.planned.reverse();
+        }
+
+        let &(cell, mines) = self.planned.last()?;
+        // Flags only cycle one at a time, so a cell needing more stays planned.
+        if mines <= 1 {
+            self.planned.pop();
+        }
+        Some((cell, mines))
+    }
+
+    /// Whether `cell` is still planned to be opened.
+    pub fn is_safe(&self, cell: usize) -> bool {
+        self.planned.contains(&(cell, 0))
+    }
+}
+
+/// Backtracking over a group's variables, keeping each constraint's running mine sum and
+/// unassigned cell count.
+struct Search {
+    targets: Vec<u32>,
+    sums: Vec<u32>,
+    open: Vec<u32>,
+    variable_constraints: Vec<Vec<usize>>,
+    max: u8,
+    values: Vec<u8>,
+    outcomes: Outcomes,
+    steps: usize,
+}
+
+impl Search {
+    fn assign(&mut self, variable: usize) {
+        self.steps += 1;
+        if self.steps > MAX_SEARCH_STEPS {
+            return;
+        }
+
+        if variable == self.values.len() {
+            let mines = self.values.iter().map(|value| *value as u32).sum();
+            let variables = self.values.len();
+            let tally = self.outcomes.entry(mines).or_insert_with(|| Tally {
+                lowest: vec![u8::MAX; variables],
+                highest: vec![0; variables],
+                count: 0.0,
+                mined: vec![0.0; variables],
+            });
+            tally.count += 1.0;
+            for (index, value) in self.values.iter().enumerate() {
+                tally.lowest[index] = tally.lowest[index].min(*value);
+                tally.highest[index] = tally.highest[index].max(*value);
+                if *value > 0 {
+                    tally.mined[index] += 1.0;
+                }
+            }
+            return;
+        }
+
+        let touched = std::mem::take(&mut self.variable_constraints[variable]);
+        for value in 0..=self.max {
+            self.values[variable] = value;
+            for constraint in &touched {
+                self.sums[*constraint] += value as u32;
+                self.open[*constraint] -= 1;
+            }
+
+            if touched.iter().all(|constraint| self.feasible(*constraint)) {
+                self.assign(variable + 1);
+            }
+
+            for constraint in &touched {
+                self.sums[*constraint] -= value as u32;
+                self.open[*constraint] += 1;
+            }
+        }
+        self.variable_constraints[variable] = touched;
+        self.values[variable] = 0;
+    }
+
+    fn feasible(&self, constraint: usize) -> bool {
+        let sum = self.sums[constraint];
+        let target = self.targets[constraint];
+        sum <= target && sum + self.open[constraint] * self.max as u32 >= target
+    }
+}
+
+/// Every total number of mines the given groups can hold together.
+fn mine_totals<'a>(groups: impl Iterator<Item = &'a Outcomes>) -> BTreeSet<u32> {
+    let mut totals = BTreeSet::from([0]);
+    for outcomes in groups {
+        totals = totals
+            .iter()
+            .flat_map(|total| outcomes.keys().map(move |mines| total + mines))
+            .collect();
+    }
+    totals
+}
+
+/// For each total the given groups can hold together, how many assignments reach it.
+fn combine_totals<'a>(groups: impl Iterator<Item = &'a BTreeMap<u32, f64>>) -> BTreeMap<u32, f64> {
+    let mut combined = BTreeMap::from([(0, 1.0)]);
+    for totals in groups {
+        let mut next = BTreeMap::new();
+        for (total, count) in &combined {
+            for (mines, ways) in totals {
+                *next.entry(total + mines).or_insert(0.0) += count * ways;
+            }
+        }
+        combined = next;
+    }
+    combined
+}
+
+/// The ways to place each number of mines up to `mines` in `cells` cells holding up to `max`
+/// each, first with one of the cells left empty and then with all of them, on a shared scale
+/// that keeps large boards in range.
+fn arrangements(cells: usize, mines: u32, max: u8) -> (Vec<f64>, Vec<f64>) {
+    let spread = |ways: &[f64]| -> Vec<f64> {
+        (0..ways.len())
+            .map(|total| {
+                (0..=total.min(max as usize))
+                    .map(|held| ways[total - held])
+                    .sum()
+            })
+            .collect()
+    };
+
+    let mut fewer = vec![0.0; mines as usize + 1];
+    fewer[0] = 1.0;
+    if cells == 0 {
+        return (vec![0.0; fewer.len()], fewer);
+    }
+    for _ in 1..cells {
+        fewer = spread(&fewer);
+        let scale = fewer.iter().copied().fold(0.0, f64::max);
+        fewer.iter_mut().for_each(|ways| *ways /= scale);
+    }
+    let all = spread(&fewer);
+    (fewer, all)
+}
+
+/// Splits constraints into groups that share no cells, so each can be searched on its own.
+fn group_constraints(constraints: &[Constraint]) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut assigned = vec![false; constraints.len()];
+
+    for start in 0..constraints.len() {
+        if assigned[start] {
+            continue;
+        }
+
+        assigned[start] = true;
+        let mut group = vec![start];
+        let mut next = 0;
+        while next < group.len() {
+            let current = group[next];
+            next += 1;
+
+            for (index, constraint) in constraints.iter().enumerate() {
+                if !assigned[index]
+                    && constraint
+                        .cells
+                        .iter()
+                        .any(|cell| constraints[current].cells.contains(cell))
+                {
+                    assigned[index] = true;
+                    group.push(index);
+                }
+            }
+        }
+        groups.push(group);
+    }
+
+    groups
+}
+
+fn group_variables(constraints: &[Constraint], group: &[usize]) -> Vec<usize> {
+    // Neighbouring cells stay close in the order, so contradictions show up early.
+    let mut variables = Vec::new();
+    for cell in group
+        .iter()
+        .flat_map(|index| constraints[*index].cells.iter().copied())
+    {
+        if !variables.contains(&cell) {
+            variables.push(cell);
+        }
+    }
+    variables
+}
+
+/// Seeds the random guesses made on the board dealt from `board_seed`. The guesses get their
+/// own stream: the board's seed would pick the first mine dealt.
+pub fn guess_seed(board_seed: u64) -> u64 {
+    board_seed.wrapping_mul(GUESS_SEED_MULTIPLIER)
+}
+
+/// Plays `layout` from its pre-revealed cells using only deductions, opening blanks the way
+/// the game does.
+pub fn solve_layout(
+    layout: &BoardLayout,
+    neighbours: &[Vec<usize>],
+    max_mines_per_cell: u8,
+) -> SolveReport {
+    let exists = |index: usize| layout.mask.as_ref().is_none_or(|mask| mask.cells[index]);
+    let number = |index: usize| -> u8 {
+        neighbours[index]
+            .iter()
+            .map(|neighbour| layout.mines[*neighbour])
+            .sum()
+    };
+
+    let mut board = SolverBoard {
+        cells: (0..layout.mines.len())
+            .map(|index| match index {
+                _ if !exists(index) => Cell::Missing,
+                _ if layout.flags[index] > 0 => Cell::Mine(layout.mines[index]),
+                _ => Cell::Hidden,
+            })
+            .collect(),
+        neighbours: neighbours.to_vec(),
+        max_mines_per_cell,
+        total_mines: layout.mine_count() as u32,
+    };
+
+    let mut to_open: Vec<usize> = (0..layout.mines.len())
+        .filter(|index| exists(*index) && layout.revealed[*index] && layout.mines[*index] == 0)
+        .collect();
+
+    loop {
+        while let Some(index) = to_open.pop() {
+            if board.cells[index] != Cell::Hidden {
+                continue;
+            }
+            let count = number(index);
+            board.cells[index] = Cell::Revealed(count);
+            if count == 0 {
+                to_open.extend(neighbours[index].iter().copied());
+            }
+        }
+
+        let deductions = board.deduce();
+        if deductions.is_empty() {
+            break;
+        }
+        for (index, mines) in deductions.mines {
+            board.cells[index] = Cell::Mine(mines);
+        }
+        to_open.extend(deductions.safe);
+    }
+
+    let safe_cells = (0..layout.mines.len())
+        .filter(|index| exists(*index) && layout.mines[*index] == 0)
+        .count();
+    let revealed_cells = board
+        .cells
+        .iter()
+        .filter(|cell| matches!(cell, Cell::Revealed(_)))
+        .count();
+
+    SolveReport {
+        solved: revealed_cells == safe_cells,
+        safe_cells,
+        revealed_cells,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A board from rows of `#` hidden, `F` a known mine, `.` no cell and digits for opened
+    /// numbers, with the eight cells around each as neighbours.
+    fn board(rows: &[&str], max_mines_per_cell: u8, total_mines: u32) -> SolverBoard {
+        let width = rows[0].len() as i32;
+        let height = rows.len() as i32;
+        let cells = rows
+            .iter()
+            .flat_map(|row| row.chars())
+            .map(|cell| match cell {
+                '#' => Cell::Hidden,
+                'F' => Cell::Mine(1),
+                '.' => Cell::Missing,
+                digit => Cell::Revealed(digit.to_digit(10).unwrap() as u8),
+            })
+            .collect();
+        let neighbours = (0..width * height)
+            .map(|index| {
+                let (x, y) = (index % width, index / width);
+                let mut around = Vec::new();
+                for ny in y - 1..=y + 1 {
+                    for nx in x - 1..=x + 1 {
+                        if (nx, ny) != (x, y)
+                            && (0..width).contains(&nx)
+                            && (0..height).contains(&ny)
+                        {
+                            around.push((ny * width + nx) as usize);
+                        }
+                    }
+                }
+                around
+            })
+            .collect();
+
+        SolverBoard {
+            cells,
+            neighbours,
+            max_mines_per_cell,
+            total_mines,
+        }
+    }
+
+    fn deduce(rows: &[&str], max_mines_per_cell: u8, total_mines: u32) -> Deductions {
+        let mut deductions = board(rows, max_mines_per_cell, total_mines).deduce();
+        deductions.safe.sort_unstable();
+        deductions.mines.sort_unstable();
+        deductions
+    }
+
+    #[test]
+    fn a_number_with_one_hidden_neighbour_is_a_mine() {
+        let deductions = deduce(&["1#"], 1, 1);
+        assert_eq!(deductions.mines, vec![(1, 1)]);
+        assert!(deductions.safe.is_empty());
+    }
+
+    #[test]
+    fn a_number_with_its_mines_flagged_clears_the_rest() {
+        let deductions = deduce(&["F1#"], 1, 1);
+        assert_eq!(deductions.safe, vec![2]);
+        assert!(deductions.mines.is_empty());
+    }
+
+    #[test]
+    fn one_two_one_has_mines_at_the_ends() {
+        let deductions = deduce(&["###", "121"], 1, 2);
+        assert_eq!(deductions.mines, vec![(0, 1), (2, 1)]);
+        assert_eq!(deductions.safe, vec![1]);
+    }
+
+    #[test]
+    fn the_mine_count_settles_cells_away_from_numbers() {
+        assert_eq!(deduce(&["##"], 1, 0).safe, vec![0, 1]);
+        assert_eq!(deduce(&["##"], 1, 2).mines, vec![(0, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn a_tie_is_left_undecided() {
+        assert!(deduce(&["#1#"], 1, 1).is_empty());
+    }
+
+    #[test]
+    fn a_frontier_holding_every_mine_clears_the_cells_beyond() {
+        let deductions = deduce(&["#1##"], 1, 1);
+        assert_eq!(deductions.safe, vec![3]);
+        assert!(deductions.mines.is_empty());
+    }
+
+    #[test]
+    fn multi_mine_cells_are_counted_by_mines() {
+        let deductions = deduce(&["2#"], 2, 2);
+        assert_eq!(deductions.mines, vec![(1, 2)]);
+    }
+
+    fn assert_chances(chances: &[f64], expected: &[(usize, f64)]) {
+        for (cell, chance) in expected {
+            assert!(
+                (chances[*cell] - chance).abs() < 1e-9,
+                "cell {cell}: {} rather than {chance}",
+                chances[*cell]
+            );
+        }
+    }
+
+    #[test]
+    fn a_cell_shared_by_two_numbers_weighs_in_the_cells_beyond() {
+        // Either the shared corner holds the mine both ones see, leaving one for the two cells
+        // in the far corner, or the other two cells by the ones hold a mine each.
+        let board = board(&["#1#..", "1....", "#..##"], 1, 2);
+        assert_chances(
+            &board.mine_chances(),
+            &[
+                (0, 2.0 / 3.0),
+                (2, 1.0 / 3.0),
+                (10, 1.0 / 3.0),
+                (13, 1.0 / 3.0),
+            ],
+        );
+        assert_ne!(board.safest_guess(), Some(0));
+    }
+
+    #[test]
+    fn the_mine_count_rules_out_placements() {
+        let board = board(&["#1#1#"], 1, 2);
+        assert_chances(&board.mine_chances(), &[(0, 1.0), (2, 0.0), (4, 1.0)]);
+        assert_eq!(board.safest_guess(), Some(2));
+    }
+
+    #[test]
+    fn multi_mine_cells_are_empty_less_often() {
+        // Two mines in two cells hiding up to two each: both in one cell twice out of three.
+        let chances = board(&["##"], 2, 2).mine_chances();
+        assert_chances(&chances, &[(0, 2.0 / 3.0), (1, 2.0 / 3.0)]);
+    }
+}
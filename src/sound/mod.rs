@@ -0,0 +1,30 @@
+pub mod resources;
+pub mod systems;
+
+use bevy::prelude::*;
+pub use resources::*;
+pub use systems::play_sound;
+use systems::*;
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AudioSettings::load())
+            .init_resource::<SoundQueue>()
+            .add_systems(Startup, setup_sound_effects)
+            .add_systems(
+                Update,
+                (toggle_mute, process_sound_queue, save_audio_settings),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    play_reveal_sounds,
+                    play_flag_sounds,
+                    play_chord_sounds,
+                    play_game_end_sounds,
+                ),
+            );
+    }
+}
@@ -0,0 +1,81 @@
+use crate::storage::{load_json, save_json};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master_volume: f32,
+    pub effects_volume: f32,
+    pub muted: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master_volume: 0.8,
+            effects_volume: 0.8,
+            muted: false,
+        }
+    }
+}
+
+impl AudioSettings {
+    const FILE_NAME: &'static str = "audio.json";
+
+    /// Volumes from an edited file are clamped to 0..=1.
+    pub fn load() -> Self {
+        let settings: Self = load_json(Self::FILE_NAME);
+        Self {
+            master_volume: settings.master_volume.clamp(0.0, 1.0),
+            effects_volume: settings.effects_volume.clamp(0.0, 1.0),
+            ..settings
+        }
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = save_json(Self::FILE_NAME, self) {
+            warn!("Failed to save audio settings: {err}");
+        }
+    }
+
+    pub fn effects_gain(&self) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            self.master_volume * self.effects_volume
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct SoundEffects {
+    pub reveal: Handle<Pitch>,
+    pub cascade: Vec<Handle<Pitch>>,
+    pub flag: Handle<Pitch>,
+    pub unflag: Handle<Pitch>,
+    pub chord: Vec<Handle<Pitch>>,
+    pub explosion: Vec<Handle<Pitch>>,
+    pub victory: Vec<Handle<Pitch>>,
+}
+
+pub struct QueuedSound {
+    pub delay: Timer,
+    pub sound: Handle<Pitch>,
+    pub gain: f32,
+}
+
+#[derive(Resource, Default)]
+pub struct SoundQueue(pub Vec<QueuedSound>);
+
+impl SoundQueue {
+    pub fn push_sequence(&mut self, sounds: &[Handle<Pitch>], interval: f32, gain: f32) {
+        for (index, sound) in sounds.iter().enumerate() {
+            self.0.push(QueuedSound {
+                delay: Timer::from_seconds(index as f32 * interval, TimerMode::Once),
+                sound: sound.clone(),
+                gain,
+            });
+        }
+    }
+}
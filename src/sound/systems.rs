@@ -0,0 +1,174 @@
+use crate::{
+    game::events::*,
+    sound::{AudioSettings, SoundEffects, SoundQueue},
+    tile::{Position, Tile, TileSprite},
+};
+use bevy::{audio::Volume, prelude::*};
+use std::time::Duration;
+
+/// Seconds the audio settings stay unchanged before they are saved.
+const AUDIO_SAVE_DELAY: f32 = 0.5;
+
+pub fn setup_sound_effects(mut commands: Commands, mut pitches: ResMut<Assets<Pitch>>) {
+    let mut note = |frequency: f32, millis: u64| {
+        pitches.add(Pitch::new(frequency, Duration::from_millis(millis)))
+    };
+
+    commands.insert_resource(SoundEffects {
+        reveal: note(880.0, 40),
+        cascade: (0..10)
+            .map(|step| note(660.0 + step as f32 * 55.0, 35))
+            .collect(),
+        flag: note(1320.0, 50),
+        unflag: note(990.0, 50),
+        chord: vec![note(740.0, 40), note(988.0, 40)],
+        explosion: vec![note(110.0, 250), note(82.0, 300), note(55.0, 400)],
+        victory: vec![
+            note(523.0, 120),
+            note(659.0, 120),
+            note(784.0, 120),
+            note(1047.0, 300),
+        ],
+    });
+}
+
+pub fn play_sound(
+    commands: &mut Commands,
+    sound: &Handle<Pitch>,
+    gain: f32,
+    audio_settings: &AudioSettings,
+) {
+    let volume = gain * audio_settings.effects_gain();
+    if volume <= 0.0 {
+        return;
+    }
+
+    commands.spawn((
+        AudioPlayer(sound.clone()),
+        PlaybackSettings::DESPAWN.with_volume(Volume::Linear(volume)),
+    ));
+}
+
+pub fn play_reveal_sounds(
+    mut commands: Commands,
+    audio_settings: Res<AudioSettings>,
+    effects: Res<SoundEffects>,
+    mut queue: ResMut<SoundQueue>,
+    mut tile_revealed_events: EventReader<TileRevealedEvent>,
+    tile_query: Query<&Tile, (With<TileSprite>, Changed<Tile>)>,
+) {
+    if tile_revealed_events.read().count() == 0 {
+        return;
+    }
+
+    let opened = tile_query
+        .iter()
+        .filter(|tile| tile.is_revealed && !tile.is_mine() && !tile.is_flagged())
+        .count();
+
+    match opened {
+        0 => {}
+        1 => play_sound(&mut commands, &effects.reveal, 1.0, &audio_settings),
+        _ => {
+            let notes = (opened / 4 + 2).min(effects.cascade.len());
+            let gain = (0.5 + opened as f32 / 40.0).min(1.0);
+            queue.push_sequence(&effects.cascade[..notes], 0.025, gain);
+        }
+    }
+}
+
+pub fn play_flag_sounds(
+    mut commands: Commands,
+    audio_settings: Res<AudioSettings>,
+    effects: Res<SoundEffects>,
+    mut tile_flagged_events: EventReader<TileFlaggedEvent>,
+    tile_query: Query<(&Position, &Tile), With<TileSprite>>,
+) {
+    for event in tile_flagged_events.read() {
+        if let Some((_, tile)) = tile_query.iter().find(|(pos, _)| **pos == event.position)
+            && !tile.is_revealed
+        {
+            let sound = if tile.is_flagged() {
+                &effects.flag
+            } else {
+                &effects.unflag
+            };
+            play_sound(&mut commands, sound, 0.8, &audio_settings);
+        }
+    }
+}
+
+pub fn play_chord_sounds(
+    effects: Res<SoundEffects>,
+    mut queue: ResMut<SoundQueue>,
+    mut chord_events: EventReader<ChordEvent>,
+) {
+    for _ in chord_events.read() {
+        queue.push_sequence(&effects.chord, 0.03, 0.8);
+    }
+}
+
+pub fn play_game_end_sounds(
+    effects: Res<SoundEffects>,
+    mut queue: ResMut<SoundQueue>,
+    mut mine_hit_events: EventReader<MineHitEvent>,
+    mut game_over_events: EventReader<GameOverEvent>,
+    mut game_won_events: EventReader<GameWonEvent>,
+) {
+    if mine_hit_events.read().count() > 0 {
+        queue.push_sequence(&effects.explosion[..1], 0.0, 0.8);
+    }
+    if game_over_events.read().count() > 0 {
+        queue.push_sequence(&effects.explosion, 0.06, 1.0);
+    }
+    if game_won_events.read().count() > 0 {
+        queue.push_sequence(&effects.victory, 0.12, 1.0);
+    }
+}
+
+pub fn process_sound_queue(
+    mut commands: Commands,
+    time: Res<Time>,
+    audio_settings: Res<AudioSettings>,
+    mut queue: ResMut<SoundQueue>,
+) {
+    queue.0.retain_mut(|queued| {
+        queued.delay.tick(time.delta());
+        if queued.delay.finished() {
+            play_sound(&mut commands, &queued.sound, queued.gain, &audio_settings);
+            false
+        } else {
+            true
+        }
+    });
+}
+
+pub fn toggle_mute(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut audio_settings: ResMut<AudioSettings>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyM) {
+        audio_settings.muted = !audio_settings.muted;
+    }
+}
+
+/// Saves once the settings have settled, so dragging a volume slider writes the file once
+/// rather than every frame.
+pub fn save_audio_settings(
+    time: Res<Time>,
+    audio_settings: Res<AudioSettings>,
+    mut unsaved_for: Local<Option<f32>>,
+) {
+    if audio_settings.is_changed() && !audio_settings.is_added() {
+        *unsaved_for = Some(0.0);
+    }
+    let Some(seconds) = unsaved_for.as_mut() else {
+        return;
+    };
+
+    *seconds += time.delta_secs();
+    if *seconds >= AUDIO_SAVE_DELAY {
+        audio_settings.save();
+        *unsaved_for = None;
+    }
+}
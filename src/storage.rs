@@ -0,0 +1,37 @@
+use serde::{Serialize, de::DeserializeOwned};
+use std::{env, fs, io, path::PathBuf};
+
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("MINESWEEPER_DATA_DIR") {
+        return dir.into();
+    }
+
+    #[cfg(target_os = "windows")]
+    if let Some(app_data) = env::var_os("APPDATA") {
+        return PathBuf::from(app_data).join("Minesweeper");
+    }
+
+    if let Some(data_home) = env::var_os("XDG_DATA_HOME") {
+        return PathBuf::from(data_home).join("minesweeper");
+    }
+    if let Some(home) = env::var_os("HOME") {
+        return PathBuf::from(home).join(".local/share/minesweeper");
+    }
+
+    PathBuf::from(".")
+}
+
+pub fn load_json<T: DeserializeOwned + Default>(file_name: &str) -> T {
+    fs::read_to_string(data_dir().join(file_name))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_json<T: Serialize>(file_name: &str, value: &T) -> io::Result<()> {
+    let path = data_dir().join(file_name);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(value)?)
+}
@@ -0,0 +1,44 @@
+#[cfg_attr(feature = "gui", derive(bevy::prelude::Component))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tile {
+    pub mines: u8,
+    pub flags: u8,
+    pub is_revealed: bool,
+    pub is_exploded: bool,
+    pub adjacent_mines: u8,
+}
+
+impl Tile {
+    pub fn is_mine(&self) -> bool {
+        self.mines > 0
+    }
+
+    pub fn is_flagged(&self) -> bool {
+        self.flags > 0
+    }
+
+    /// Steps through 1..=`max_flags` flags and back to none.
+    pub fn cycle_flag(&mut self, max_flags: u8) {
+        self.flags = (self.flags + 1) % (max_flags + 1);
+    }
+
+    pub fn reveal(&mut self) {
+        self.is_revealed = true;
+    }
+
+    pub fn explode(&mut self) {
+        self.is_revealed = true;
+        self.is_exploded = true;
+    }
+
+    pub fn is_wrong_flag(&self) -> bool {
+        self.is_revealed && self.is_flagged() && self.flags != self.mines
+    }
+}
+
+#[cfg_attr(feature = "gui", derive(bevy::prelude::Component))]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Position {
+    pub x: u8,
+    pub y: u8,
+}
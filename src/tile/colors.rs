@@ -0,0 +1,87 @@
+use crate::tile::Tile;
+
+/// An sRGB colour, kept free of bevy so the terminal paints the window's palette.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const LIGHT_GRAY: Rgb = Rgb(211, 211, 211);
+    pub const MAROON: Rgb = Rgb(128, 0, 0);
+    pub const ORANGE: Rgb = Rgb(255, 165, 0);
+    pub const RED: Rgb = Rgb(255, 0, 0);
+    pub const YELLOW: Rgb = Rgb(255, 255, 0);
+
+    /// Relative luminance, from 0 for black to 1 for white.
+    pub fn luminance(&self) -> f32 {
+        let linear = |channel: u8| {
+            let channel = channel as f32 / 255.0;
+            if channel <= 0.04045 {
+                channel / 12.92
+            } else {
+                ((channel + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(self.0) + 0.7152 * linear(self.1) + 0.0722 * linear(self.2)
+    }
+}
+
+#[cfg(feature = "gui")]
+impl From<Rgb> for bevy::color::Color {
+    fn from(Rgb(red, green, blue): Rgb) -> Self {
+        Self::srgb_u8(red, green, blue)
+    }
+}
+
+pub fn number_color(mines: u8) -> Rgb {
+    match mines {
+        1 => Rgb(0, 0, 255),     // Blue
+        2 => Rgb(0, 128, 0),     // Green
+        3 => Rgb(255, 0, 0),     // Red
+        4 => Rgb(128, 0, 128),   // Purple
+        5 => Rgb(128, 0, 0),     // Dark Red
+        6 => Rgb(0, 128, 128),   // Teal
+        7 => Rgb(0, 0, 0),       // Black
+        8 => Rgb(128, 128, 128), // Gray
+        _ => Rgb(255, 255, 255),
+    }
+}
+
+pub fn fill_color(tile: &Tile) -> Rgb {
+    if tile.is_wrong_flag() {
+        Rgb::ORANGE
+    } else if tile.is_revealed {
+        if tile.is_exploded {
+            Rgb::RED
+        } else if tile.is_mine() {
+            Rgb::MAROON
+        } else {
+            Rgb::BLACK
+        }
+    } else if tile.is_flagged() {
+        Rgb::YELLOW
+    } else {
+        Rgb::LIGHT_GRAY
+    }
+}
+
+/// Text shown on a tile: its number once opened, and on multi-mine boards the flag or mine count.
+pub fn label(tile: &Tile, multi_mine: bool) -> Option<(String, Rgb)> {
+    if tile.is_revealed && !tile.is_mine() && !tile.is_flagged() {
+        (tile.adjacent_mines > 0).then(|| {
+            (
+                tile.adjacent_mines.to_string(),
+                number_color(tile.adjacent_mines),
+            )
+        })
+    } else if !multi_mine {
+        None
+    } else if tile.is_revealed && tile.is_mine() {
+        Some((tile.mines.to_string(), Rgb::WHITE))
+    } else if tile.is_flagged() {
+        Some((tile.flags.to_string(), Rgb::BLACK))
+    } else {
+        None
+    }
+}
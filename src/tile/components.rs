@@ -1,29 +1,9 @@
+use crate::{
+    animation::TileAnimation,
+    tile::{Position, Tile},
+};
 use bevy::prelude::*;
 
-#[derive(Component, Clone, Debug)]
-pub struct Tile {
-    pub is_mine: bool,
-    pub is_flagged: bool,
-    pub is_revealed: bool,
-    pub adjacent_mines: u8,
-}
-
-impl Tile {
-    pub fn toggle_flag(&mut self) {
-        self.is_flagged = !self.is_flagged;
-    }
-
-    pub fn reveal(&mut self) {
-        self.is_revealed = true;
-    }
-}
-
-#[derive(Component, Copy, Clone, Debug, PartialEq)]
-pub struct Position {
-    pub x: u8,
-    pub y: u8,
-}
-
 #[derive(Component, Debug)]
 pub struct TileSprite;
 
@@ -37,6 +17,7 @@ pub struct TileBundle {
     pub position: Position,
     pub transform: Transform,
     pub tile_sprite: TileSprite,
+    pub animation: TileAnimation,
 }
 
 #[derive(Bundle)]
@@ -1,14 +1,24 @@
+pub mod cell;
+pub mod colors;
+#[cfg(feature = "gui")]
 pub mod components;
+#[cfg(feature = "gui")]
 pub mod systems;
 
+#[cfg(feature = "gui")]
 use bevy::prelude::*;
+pub use cell::*;
+#[cfg(feature = "gui")]
 pub use components::*;
+#[cfg(feature = "gui")]
 use systems::*;
 
+#[cfg(feature = "gui")]
 pub struct TilePlugin;
 
+#[cfg(feature = "gui")]
 impl Plugin for TilePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, render_tiles);
+        app.add_systems(PostUpdate, render_tiles);
     }
 }
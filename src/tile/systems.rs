@@ -1,35 +1,54 @@
-use crate::tile::{Position, Tile, TileSprite, TileText};
-use bevy::{
-    color::palettes::css::{BLACK, LIGHT_GRAY, RED, YELLOW},
-    prelude::*,
+use crate::{
+    animation::{AnimationSettings, TileAnimation},
+    board::BoardSettings,
+    tile::{
+        Position, Tile, TileSprite, TileText,
+        colors::{fill_color, label},
+    },
 };
+use bevy::prelude::*;
 
+pub fn tile_color(tile: &Tile) -> Color {
+    fill_color(tile).into()
+}
+
+pub fn tile_label(tile: &Tile, multi_mine: bool) -> Option<(String, Color)> {
+    label(tile, multi_mine).map(|(text, color)| (text, color.into()))
+}
+
+#[allow(clippy::type_complexity)]
 pub fn render_tiles(
+    settings: Res<BoardSettings>,
+    animation_settings: Res<AnimationSettings>,
     mut tile_sprite_query: Query<(&Tile, &mut Sprite), (With<TileSprite>, Changed<Tile>)>,
-    mut tile_text_query: Query<(&Position, &mut Visibility), With<TileText>>,
-    tile_data_query: Query<(&Position, &Tile), With<TileSprite>>,
+    mut tile_text_query: Query<
+        (&Position, &mut Visibility, &mut Text2d, &mut TextColor),
+        With<TileText>,
+    >,
+    tile_data_query: Query<(&Position, &Tile, &TileAnimation), With<TileSprite>>,
 ) {
-    for (tile, mut sprite) in tile_sprite_query.iter_mut() {
-        if tile.is_revealed {
-            sprite.color = if tile.is_mine {
-                RED.into()
-            } else {
-                BLACK.into()
-            };
-        } else if tile.is_flagged {
-            sprite.color = YELLOW.into();
-        } else {
-            sprite.color = LIGHT_GRAY.into();
+    if !animation_settings.enabled {
+        for (tile, mut sprite) in tile_sprite_query.iter_mut() {
+            sprite.color = tile_color(tile);
         }
     }
 
-    for (text_pos, mut visibility) in tile_text_query.iter_mut() {
-        if let Some((_, tile)) = tile_data_query.iter().find(|(pos, _)| **pos == *text_pos) {
-            *visibility = if tile.is_revealed && !tile.is_mine {
-                Visibility::Visible
-            } else {
-                Visibility::Hidden
-            };
+    for (text_pos, mut visibility, mut text, mut text_color) in tile_text_query.iter_mut() {
+        if let Some((_, tile, animation)) =
+            tile_data_query.iter().find(|(pos, ..)| **pos == *text_pos)
+        {
+            match tile_label(tile, settings.multi_mine).filter(|_| !animation.is_active()) {
+                Some((label, color)) => {
+                    if text.0 != label {
+                        text.0 = label;
+                    }
+                    text_color.set_if_neq(TextColor(color));
+                    visibility.set_if_neq(Visibility::Visible);
+                }
+                None => {
+                    visibility.set_if_neq(Visibility::Hidden);
+                }
+            }
         }
     }
 }
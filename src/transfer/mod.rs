@@ -0,0 +1,27 @@
+pub mod components;
+pub mod resources;
+pub mod systems;
+
+use crate::mode::systems::start_run;
+use bevy::prelude::*;
+pub use components::*;
+pub use resources::*;
+use systems::*;
+
+pub struct TransferPlugin;
+
+impl Plugin for TransferPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TransferStatus>()
+            .add_systems(Startup, spawn_transfer_status)
+            .add_systems(
+                Update,
+                (
+                    export_board,
+                    import_board.before(start_run),
+                    update_transfer_status,
+                )
+                    .chain(),
+            );
+    }
+}
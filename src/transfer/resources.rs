@@ -0,0 +1,49 @@
+use crate::storage::data_dir;
+use bevy::prelude::*;
+use std::{fs, path::PathBuf};
+
+const STATUS_SECONDS: f32 = 4.0;
+
+/// Where exported boards are written and where the import key looks for them.
+pub fn boards_dir() -> PathBuf {
+    data_dir().join("boards")
+}
+
+/// Board files in the boards folder, sorted by name.
+pub fn board_files() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(boards_dir()) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// The last import or export result, shown for a few seconds.
+#[derive(Resource)]
+pub struct TransferStatus {
+    pub message: String,
+    pub timer: Timer,
+}
+
+impl Default for TransferStatus {
+    fn default() -> Self {
+        Self {
+            message: String::new(),
+            timer: Timer::from_seconds(STATUS_SECONDS, TimerMode::Once),
+        }
+    }
+}
+
+impl TransferStatus {
+    pub fn show(&mut self, message: String) {
+        info!("{message}");
+        self.message = message;
+        self.timer.reset();
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{
+    board::{
+        BoardSettings,
+        formats::{BoardFormat, BoardInfo},
+        layout::BoardLayout,
+        systems::current_layout,
+    },
+    editor::Editor,
+    game::{GameState, events::NewGameEvent},
+    mode::{
+        GameMode,
+        daily::{date_string, today},
+    },
+    replay::systems::is_replay_file,
+    tile::{Position, Tile, TileSprite},
+    transfer::*,
+};
+use bevy::prelude::*;
+use std::{fs, path::Path};
+
+const STATUS_FONT_SIZE: f32 = 14.0;
+
+pub fn spawn_transfer_status(mut commands: Commands) {
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font_size: STATUS_FONT_SIZE,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(6.0),
+            right: Val::Px(8.0),
+            padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0., 0., 0., 0.6)),
+        Visibility::Hidden,
+        TransferStatusText,
+    ));
+}
+
+/// Writes the board in play, or the one in the editor, to the boards folder in every format.
+pub fn export_board(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    state: Res<State<GameState>>,
+    settings: Res<BoardSettings>,
+    editor: Res<Editor>,
+    tile_query: Query<(&Position, &Tile), With<TileSprite>>,
+    mut status: ResMut<TransferStatus>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyX) {
+        return;
+    }
+
+    let layout = match state.get() {
+        GameState::Editing => editor.layout.clone(),
+        _ => current_layout(&settings, tile_query.iter()),
+    };
+
+    let date = date_string(today());
+    let dir = boards_dir();
+    let name = (1..)
+        .map(|number| format!("board-{date}-{number}"))
+        .find(|name| {
+            BoardFormat::ALL
+                .iter()
+                .all(|format| !dir.join(format!("{name}.{}", format.extension())).exists())
+        })
+        .unwrap_or_default();
+    let info = BoardInfo {
+        title: name.clone(),
+        created: date,
+        seed: settings.seed,
+    };
+
+    if let Err(err) = fs::create_dir_all(&dir) {
+        status.show(format!("Could not export: {err}"));
+        return;
+    }
+
+    let mut written = Vec::new();
+    let mut failed = Vec::new();
+    for format in BoardFormat::ALL {
+        let path = dir.join(format!("{name}.{}", format.extension()));
+        match format
+            .export(&layout, &info)
+            .map_err(|err| err.to_string())
+            .and_then(|bytes| fs::write(&path, bytes).map_err(|err| err.to_string()))
+        {
+            Ok(()) => written.push(format!(".{}", format.extension())),
+            Err(err) => failed.push(format!(".{}: {err}", format.extension())),
+        }
+    }
+
+    let mut message = format!("Exported {name} ({})", written.join(" "));
+    if !failed.is_empty() {
+        message.push_str(&format!("\nSkipped {}", failed.join(", ")));
+    }
+    status.show(message);
+}
+
+/// Loads boards dropped onto the window, or the next file in the boards folder on `B`, into the
+/// editor when it is open and as a new game otherwise.
+#[allow(clippy::too_many_arguments)]
+pub fn import_board(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    state: Res<State<GameState>>,
+    mut drop_events: EventReader<FileDragAndDrop>,
+    mut settings: ResMut<BoardSettings>,
+    mut editor: ResMut<Editor>,
+    mut game_mode: ResMut<GameMode>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+    mut status: ResMut<TransferStatus>,
+    mut next_file: Local<usize>,
+) {
+    let mut paths: Vec<_> = drop_events
+        .read()
+        .filter_map(|event| match event {
+            FileDragAndDrop::DroppedFile { path_buf, .. } if !is_replay_file(path_buf) => {
+                Some(path_buf.clone())
+            }
+            _ => None,
+        })
+        .collect();
+
+    if keyboard_input.just_pressed(KeyCode::KeyB) {
+        let files = board_files();
+        if files.is_empty() {
+            status.show(format!("No board files in {}", boards_dir().display()));
+        } else {
+            *next_file %= files.len();
+            paths.push(files[*next_file].clone());
+            *next_file += 1;
+        }
+    }
+
+    // Only the last of several boards would stay loaded, so skip straight to it.
+    let Some(path) = paths.pop() else {
+        return;
+    };
+
+    let layout = match read_board(&path) {
+        Ok(layout) => layout,
+        Err(err) => {
+            status.show(format!("Could not import {}: {err}", file_name(&path)));
+            return;
+        }
+    };
+
+    status.show(format!("Imported {}", file_name(&path)));
+    if *state.get() == GameState::Editing {
+        editor.layout = layout;
+        editor.loaded = None;
+    } else {
+        settings.apply_layout(layout);
+        game_mode.set_if_neq(GameMode::Classic);
+        new_game_events.write(NewGameEvent);
+    }
+}
+
+fn read_board(path: &Path) -> Result<BoardLayout, String> {
+    let format = BoardFormat::from_path(path).map_err(|err| err.to_string())?;
+    let bytes = fs::read(path).map_err(|err| err.to_string())?;
+
+    format
+        .import(&file_name(path), &bytes)
+        .map_err(|err| err.to_string())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map_or_else(String::new, |name| name.to_string_lossy().to_string())
+}
+
+pub fn update_transfer_status(
+    time: Res<Time>,
+    mut status: ResMut<TransferStatus>,
+    mut text_query: Single<(&mut Text, &mut Visibility), With<TransferStatusText>>,
+) {
+    let (text, visibility) = &mut *text_query;
+
+    if status.is_changed() && !status.message.is_empty() {
+        text.0 = status.message.clone();
+        **visibility = Visibility::Visible;
+    }
+
+    status.timer.tick(time.delta());
+    if status.timer.just_finished() {
+        **visibility = Visibility::Hidden;
+    }
+}
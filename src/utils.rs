@@ -1,7 +1,106 @@
-pub fn calculate_tile_x(index_x: u8, width: u8, tile_size: f32) -> f32 {
-    (index_x as f32 * tile_size) - ((tile_size / 2.) * (width - 1) as f32)
+use crate::{
+    board::{BoardSettings, BoardShape, topology::Triangle},
+    tile::Position,
+};
+use bevy::prelude::*;
+
+// Vertical distance between hex row centres, as a fraction of the hex width.
+const HEX_ROW_SPACING: f32 = 0.866_025_4;
+// Point-to-point height of a pointy-top hex, as a fraction of its width.
+const HEX_HEIGHT: f32 = 1.154_700_5;
+// Height of an equilateral triangle, as a fraction of its side.
+const TRIANGLE_HEIGHT: f32 = 0.866_025_4;
+
+pub fn tile_translation(position: Position, settings: &BoardSettings) -> Vec2 {
+    let tile_size = settings.tile_size;
+
+    match settings.shape {
+        BoardShape::Square => Vec2::new(
+            (position.x as f32 * tile_size) - ((tile_size / 2.) * (settings.width - 1) as f32),
+            (position.y as f32 * tile_size) - ((tile_size / 2.) * (settings.height - 1) as f32),
+        ),
+        BoardShape::Hex => {
+            let row_offset = if position.y % 2 == 1 { 0.5 } else { 0.0 };
+            let row_spacing = tile_size * HEX_ROW_SPACING;
+
+            Vec2::new(
+                (position.x as f32 + row_offset) * tile_size
+                    - (tile_size / 2.) * (settings.width as f32 - 0.5),
+                (position.y as f32 * row_spacing)
+                    - ((row_spacing / 2.) * (settings.height - 1) as f32),
+            )
+        }
+        BoardShape::Triangle => {
+            let row_spacing = tile_size * TRIANGLE_HEIGHT;
+
+            Vec2::new(
+                (position.x as f32 - (settings.width - 1) as f32 / 2.) * tile_size / 2.,
+                (position.y as f32 * row_spacing)
+                    - ((row_spacing / 2.) * (settings.height - 1) as f32),
+            )
+        }
+    }
+}
+
+pub fn tile_sprite_size(settings: &BoardSettings) -> Vec2 {
+    match settings.shape {
+        BoardShape::Square => Vec2::splat(settings.tile_size),
+        BoardShape::Hex => Vec2::new(settings.tile_size, settings.tile_size * HEX_HEIGHT),
+        BoardShape::Triangle => Vec2::new(settings.tile_size, settings.tile_size * TRIANGLE_HEIGHT),
+    }
+}
+
+/// Size of the whole board measured in tiles, used to fit and frame the board.
+pub fn board_extent(settings: &BoardSettings) -> Vec2 {
+    match settings.shape {
+        BoardShape::Square => Vec2::new(settings.width as f32, settings.height as f32),
+        BoardShape::Hex => Vec2::new(
+            settings.width as f32 + 0.5,
+            (settings.height - 1) as f32 * HEX_ROW_SPACING + HEX_HEIGHT,
+        ),
+        BoardShape::Triangle => Vec2::new(
+            (settings.width as f32 + 1.) / 2.,
+            settings.height as f32 * TRIANGLE_HEIGHT,
+        ),
+    }
+}
+
+/// Converts a world point to board units: tile widths right and down from the board's top-left
+/// corner.
+pub fn world_to_board(point: Vec2, settings: &BoardSettings) -> Vec2 {
+    let corner = board_extent(settings) * settings.tile_size * Vec2::new(-0.5, 0.5);
+    Vec2::new(point.x - corner.x, corner.y - point.y) / settings.tile_size
+}
+
+pub fn board_to_world(point: Vec2, settings: &BoardSettings) -> Vec2 {
+    let corner = board_extent(settings) * settings.tile_size * Vec2::new(-0.5, 0.5);
+    corner + Vec2::new(point.x, -point.y) * settings.tile_size
+}
+
+pub fn contains_point(position: Position, point: Vec2, settings: &BoardSettings) -> bool {
+    let offset = point - tile_translation(position, settings);
+    let half_size = tile_sprite_size(settings) / 2.;
+
+    match settings.shape {
+        BoardShape::Square => offset.x.abs() <= half_size.x && offset.y.abs() <= half_size.y,
+        BoardShape::Hex => {
+            offset.x.abs() <= half_size.x
+                && offset.y.abs() <= half_size.y - offset.x.abs() * half_size.y / (2. * half_size.x)
+        }
+        BoardShape::Triangle => {
+            let rise = if Triangle::points_up(position) {
+                offset.y
+            } else {
+                -offset.y
+            };
+
+            rise.abs() <= half_size.y
+                && offset.x.abs() <= (half_size.y - rise) * half_size.x / (2. * half_size.y)
+        }
+    }
 }
 
-pub fn calculate_tile_y(index_y: u8, height: u8, tile_size: f32) -> f32 {
-    (index_y as f32 * tile_size) - ((tile_size / 2.) * (height - 1) as f32)
+/// Triangle sprites are drawn pointing up, so downward cells mirror them vertically.
+pub fn tile_flipped(position: Position, settings: &BoardSettings) -> bool {
+    settings.shape == BoardShape::Triangle && !Triangle::points_up(position)
 }
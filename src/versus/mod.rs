@@ -0,0 +1,65 @@
+pub mod protocol;
+pub mod resources;
+pub mod systems;
+
+use crate::{
+    accessibility::systems::handle_cursor_action,
+    autoplay::systems::run_autoplay,
+    board::systems::reset_board,
+    game::{
+        GameState,
+        events::NewGameEvent,
+        systems::{handle_chord_tile, handle_tile_flagged, handle_tile_revealed},
+    },
+    input::systems::handle_mouse_input,
+    mode::systems::{apply_daily_preset, start_run},
+    net::NetConfig,
+    replay::systems::run_playback,
+};
+use bevy::prelude::*;
+pub use protocol::*;
+pub use resources::*;
+use systems::*;
+
+/// Two players taking turns on one board, at one screen or over the LAN, where each mine
+/// found scores and earns another turn.
+pub struct VersusPlugin;
+
+impl Plugin for VersusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NetConfig>()
+            .init_resource::<VersusNet>()
+            .init_resource::<Versus>()
+            .add_systems(Startup, open_versus_connection)
+            .add_systems(
+                Update,
+                deal_versus_board
+                    .after(apply_daily_preset)
+                    .before(reset_board)
+                    .run_if(on_event::<NewGameEvent>),
+            )
+            .add_systems(
+                Update,
+                (
+                    forward_versus_moves
+                        .after(handle_mouse_input)
+                        .after(handle_cursor_action)
+                        .after(run_autoplay)
+                        .after(run_playback),
+                    // The opponent's moves must not be taken for this player's.
+                    receive_versus_messages.before(start_run),
+                )
+                    .chain()
+                    .before(handle_tile_revealed)
+                    .before(handle_tile_flagged)
+                    .before(handle_chord_tile),
+            )
+            .add_systems(
+                Update,
+                take_versus_turns
+                    .after(handle_tile_revealed)
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not(on_event::<NewGameEvent>)),
+            );
+    }
+}
@@ -0,0 +1,24 @@
+use crate::net::SharedBoard;
+use serde::{Deserialize, Serialize};
+
+/// Both players deal the same board and play each other's moves on it, so only the moves
+/// travel. `round` counts the boards dealt, so a move made on a board since replaced is
+/// dropped.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VersusMessage {
+    Hello {
+        name: String,
+    },
+    Start {
+        board: SharedBoard,
+        round: u32,
+        names: [String; 2],
+    },
+    Move {
+        round: u32,
+        x: u8,
+        y: u8,
+    },
+    Restart,
+}
@@ -0,0 +1,85 @@
+use crate::{
+    net::{Client, Host, PeerId, SharedBoard},
+    versus::VersusMessage,
+};
+use bevy::prelude::*;
+
+/// The host sits in the first seat and moves first on odd rounds.
+pub const SEATS: usize = 2;
+
+#[derive(Resource, Default)]
+pub enum VersusNet {
+    #[default]
+    Off,
+    Host(Host<VersusMessage>),
+    Client(Client<VersusMessage>),
+}
+
+#[derive(Resource, Debug)]
+pub struct Versus {
+    /// This player's seat over the network; hot-seat players share the mouse and have none.
+    pub me: Option<usize>,
+    /// The other instance, once there is one to play.
+    pub opponent: Option<PeerId>,
+    pub names: [String; SEATS],
+    pub scores: [u16; SEATS],
+    pub turn: usize,
+    /// Boards dealt so far, which decides who opens each one.
+    pub round: u32,
+    /// Mines on the board, so more than half of them wins.
+    pub mines: u16,
+    pub board: Option<SharedBoard>,
+    /// Set while dealing the host's board, which is not a restart of this player's own.
+    pub dealing: bool,
+    pub status: String,
+}
+
+impl Default for Versus {
+    fn default() -> Self {
+        Self {
+            me: None,
+            opponent: None,
+            names: ["Player 1".to_string(), "Player 2".to_string()],
+            scores: [0; SEATS],
+            turn: 0,
+            round: 0,
+            mines: 0,
+            board: None,
+            dealing: false,
+            status: String::new(),
+        }
+    }
+}
+
+impl Versus {
+    pub fn first(&self) -> usize {
+        (self.round as usize + 1) % SEATS
+    }
+
+    /// Whether the mouse here may move: always in hot-seat, on this player's turn online.
+    pub fn is_local_turn(&self) -> bool {
+        match self.me {
+            None => true,
+            Some(me) => self.opponent.is_some() && self.turn == me,
+        }
+    }
+
+    pub fn pass_turn(&mut self) {
+        self.turn = (self.turn + 1) % SEATS;
+    }
+
+    /// Over once someone holds more than half the mines, or none are left to find.
+    pub fn is_decided(&self) -> bool {
+        self.scores.iter().any(|score| score * 2 > self.mines)
+            || self.scores.iter().sum::<u16>() >= self.mines
+    }
+
+    /// The seat with more mines, or none when level.
+    pub fn leader(&self) -> Option<usize> {
+        match self.scores[0].cmp(&self.scores[1]) {
+            std::cmp::Ordering::Greater => Some(0),
+            std::cmp::Ordering::Less => Some(1),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{
+    autoplay::Autoplay,
+    board::BoardSettings,
+    coop::FlagOwners,
+    game::{GameState, events::*, systems::show_overlay_text},
+    mode::{GameMode, ModeRecords, ModeRun},
+    net::{Client, HOST, Host, NetConfig, NetEvent, NetGame, NetRole, SharedBoard},
+    tile::{Position, Tile, TileSprite},
+    versus::*,
+};
+use bevy::{ecs::event::EventCursor, prelude::*};
+
+/// Hot-seat starts right away and the host waits in versus mode for an opponent; a player
+/// joining waits for the host's board.
+pub fn open_versus_connection(
+    config: Res<NetConfig>,
+    mut net: ResMut<VersusNet>,
+    mut versus: ResMut<Versus>,
+    mut mode: ResMut<GameMode>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+) {
+    if config.game != NetGame::Versus {
+        return;
+    }
+    match &config.role {
+        NetRole::Offline => {
+            *mode = GameMode::Versus;
+            new_game_events.write(NewGameEvent);
+        }
+        NetRole::Host { port } => match Host::bind(*port, SEATS - 1) {
+            Ok(host) => {
+                versus.me = Some(0);
+                versus.names[0] = config.name.clone();
+                versus.status = format!("Hosting on port {}", host.port);
+                *net = VersusNet::Host(host);
+                *mode = GameMode::Versus;
+                new_game_events.write(NewGameEvent);
+            }
+            Err(err) => versus.status = format!("Could not host on port {port}: {err}"),
+        },
+        NetRole::Join { address } => match Client::connect(address) {
+            Ok(client) => {
+                client.send(&VersusMessage::Hello {
+                    name: config.name.clone(),
+                });
+                versus.me = Some(1);
+                versus.status = format!("Joined {address}, waiting for the host");
+                *net = VersusNet::Client(client);
+            }
+            Err(err) => versus.status = format!("Could not join {address}: {err}"),
+        },
+    }
+}
+
+/// Plays the opponent's moves on this board, and deals a new one when either player asks.
+pub fn receive_versus_messages(
+    mut net: ResMut<VersusNet>,
+    mut versus: ResMut<Versus>,
+    state: Res<State<GameState>>,
+    mut mode: ResMut<GameMode>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+    mut tile_revealed_events: EventWriter<TileRevealedEvent>,
+) {
+    let playing = *state.get() == GameState::Playing;
+    let mut play = |versus: &Versus, round: u32, x: u8, y: u8| {
+        if playing && round == versus.round && !versus.is_local_turn() {
+            tile_revealed_events.write(TileRevealedEvent {
+                position: Position { x, y },
+            });
+        }
+    };
+
+    let mut host_left = false;
+    match &*net {
+        VersusNet::Off => {}
+        VersusNet::Host(host) => {
+            for event in host.poll() {
+                match event {
+                    NetEvent::Message(id, VersusMessage::Hello { name }) => {
+                        versus.opponent = Some(id);
+                        versus.status = format!("{name} joined");
+                        versus.names[1] = name;
+                        *mode = GameMode::Versus;
+                        new_game_events.write(NewGameEvent);
+                    }
+                    NetEvent::Message(id, VersusMessage::Move { round, x, y })
+                        if versus.opponent == Some(id) =>
+                    {
+                        play(&versus, round, x, y);
+                    }
+                    NetEvent::Message(id, VersusMessage::Restart)
+                        if versus.opponent == Some(id) =>
+                    {
+                        *mode = GameMode::Versus;
+                        new_game_events.write(NewGameEvent);
+                    }
+                    NetEvent::Left(id) if versus.opponent == Some(id) => {
+                        versus.opponent = None;
+                        versus.status = format!("{} has left", versus.names[1]);
+                    }
+                    NetEvent::Joined(_) | NetEvent::Message(..) | NetEvent::Left(_) => {}
+                }
+            }
+        }
+        VersusNet::Client(client) => {
+            for message in client.poll() {
+                match message {
+                    Some(VersusMessage::Start {
+                        board,
+                        round,
+                        names,
+                    }) => {
+                        versus.opponent = Some(HOST);
+                        versus.board = Some(board);
+                        versus.round = round;
+                        versus.names = names;
+                        versus.status.clear();
+                        versus.dealing = true;
+                        *mode = GameMode::Versus;
+                        new_game_events.write(NewGameEvent);
+                    }
+                    Some(VersusMessage::Move { round, x, y }) => play(&versus, round, x, y),
+                    Some(_) => {}
+                    None => host_left = true,
+                }
+            }
+        }
+    }
+
+    if host_left {
+        *net = VersusNet::Off;
+        versus.opponent = None;
+        versus.status = "The host has left".to_string();
+    }
+}
+
+/// Every board starts level, with the players taking turns to open. The host deals online
+/// and a player's own restart asks the host for a board.
+pub fn deal_versus_board(
+    net: Res<VersusNet>,
+    run: Res<ModeRun>,
+    mut versus: ResMut<Versus>,
+    mut settings: ResMut<BoardSettings>,
+    mut flag_owners: ResMut<FlagOwners>,
+) {
+    // Claims belong to the board being replaced.
+    if !flag_owners.0.is_empty() {
+        flag_owners.0.clear();
+    }
+    if run.mode != GameMode::Versus {
+        return;
+    }
+
+    match &*net {
+        VersusNet::Off => versus.round += 1,
+        VersusNet::Host(host) => {
+            if versus.opponent.is_some() {
+                versus.round += 1;
+                let board = SharedBoard::from_settings(&settings, rand::random());
+                host.broadcast(&VersusMessage::Start {
+                    board: board.clone(),
+                    round: versus.round,
+                    names: versus.names.clone(),
+                });
+                versus.board = Some(board);
+            }
+        }
+        VersusNet::Client(client) => {
+            // Until the host deals, this copy of the board is not the one being played.
+            if !versus.dealing {
+                client.send(&VersusMessage::Restart);
+                versus.opponent = None;
+            }
+            versus.dealing = false;
+        }
+    }
+
+    if !matches!(*net, VersusNet::Off)
+        && let Some(board) = &versus.board
+    {
+        board.apply(&mut settings);
+    }
+    versus.scores = [0; SEATS];
+    versus.turn = versus.first();
+    versus.mines = settings.mine_count as u16;
+}
+
+/// Flags was played with reveals alone, and only by the player whose turn it is; online,
+/// those moves are sent to the opponent as they are played here. The solver's moves are never
+/// sent.
+#[allow(clippy::too_many_arguments)]
+pub fn forward_versus_moves(
+    run: Res<ModeRun>,
+    net: Res<VersusNet>,
+    versus: Res<Versus>,
+    autoplay: Res<Autoplay>,
+    mut tile_revealed_events: ResMut<Events<TileRevealedEvent>>,
+    mut tile_flagged_events: ResMut<Events<TileFlaggedEvent>>,
+    mut chord_events: ResMut<Events<ChordEvent>>,
+    mut played: Local<EventCursor<TileRevealedEvent>>,
+) {
+    let played: Vec<Position> = played
+        .read(&tile_revealed_events)
+        .map(|event| event.position)
+        .collect();
+    if run.mode != GameMode::Versus {
+        return;
+    }
+    tile_flagged_events.clear();
+    chord_events.clear();
+
+    let solver_online = autoplay.enabled && !matches!(*net, VersusNet::Off);
+    if !versus.is_local_turn() || solver_online {
+        tile_revealed_events.clear();
+        return;
+    }
+    let round = versus.round;
+    for Position { x, y } in played {
+        let message = VersusMessage::Move { round, x, y };
+        match &*net {
+            VersusNet::Off => {}
+            VersusNet::Host(host) => host.broadcast(&message),
+            VersusNet::Client(client) => client.send(&message),
+        }
+    }
+}
+
+/// Scores the mines found this frame to whoever found them, who goes again; opening safe
+/// cells passes the turn. The board ends once the result cannot change.
+pub fn take_versus_turns(
+    mut commands: Commands,
+    run: Res<ModeRun>,
+    mut versus: ResMut<Versus>,
+    mut flag_owners: ResMut<FlagOwners>,
+    mut records: ResMut<ModeRecords>,
+    mut next_state: ResMut<NextState<GameState>>,
+    tile_query: Query<(&Position, Ref<Tile>), With<TileSprite>>,
+) {
+    if run.mode != GameMode::Versus {
+        return;
+    }
+
+    let mut found = 0;
+    let mut opened = false;
+    for (position, tile) in tile_query.iter() {
+        if !tile.is_changed() || tile.is_added() {
+            continue;
+        }
+        if tile.is_exploded && !flag_owners.0.contains_key(position) {
+            flag_owners.0.insert(*position, versus.turn as u8);
+            found += tile.mines as u16;
+        } else if tile.is_revealed && !tile.is_mine() {
+            opened = true;
+        }
+    }
+
+    if found == 0 {
+        if opened {
+            versus.pass_turn();
+        }
+        return;
+    }
+    let turn = versus.turn;
+    versus.scores[turn] += found;
+    if !versus.is_decided() {
+        return;
+    }
+
+    next_state.set(GameState::GameOver);
+    let summary = match versus.leader() {
+        Some(winner) => format!(
+            "{} wins {} to {}",
+            versus.names[winner],
+            versus.scores[winner],
+            versus.scores[1 - winner]
+        ),
+        None => format!("A draw at {} mines each", versus.scores[0]),
+    };
+    show_overlay_text(&mut commands, format!("{summary}. Press R to restart"));
+
+    // Hot-seat games have no player of this screen to credit.
+    if let Some(me) = versus.me {
+        records.record(GameMode::Versus, (versus.leader() == Some(me)) as u32);
+    }
+}